@@ -0,0 +1,43 @@
+//! Draw a spinning triangle in a window until it's closed or Escape is
+//! pressed.
+//!
+//! Run with `cargo run --example window --features window`.
+
+extern crate minifb;
+extern crate qr;
+
+use std::iter;
+use std::time::Duration;
+
+use minifb::{Key, Window, WindowOptions};
+
+use qr::{Renderer, SimpleRenderer, Triangle};
+
+const WIDTH: usize = 320;
+const HEIGHT: usize = 240;
+
+fn main() {
+    let mut window = Window::new("qr", WIDTH, HEIGHT, WindowOptions::default())
+        .expect("couldn't open a window");
+    window.limit_update_rate(Some(Duration::from_millis(16)));
+
+    let mut renderer = SimpleRenderer::<(u8, u8, u8)>::new(WIDTH, HEIGHT);
+    let (cx, cy) = (WIDTH as f64 / 2.0, HEIGHT as f64 / 2.0);
+    let mut angle = 0.0_f64;
+
+    while window.is_open() && !window.is_key_down(Key::Escape) {
+        let vertex = |i: f64| {
+            let a = angle + i * 2.0 * std::f64::consts::PI / 3.0;
+            (cx + a.cos() * 100.0, cy + a.sin() * 100.0)
+        };
+        let triangle = Triangle::with_points([vertex(0.0), vertex(1.0), vertex(2.0)]);
+
+        renderer.clear((0, 0, 0));
+        renderer.set_attr(0, (255, 255, 255));
+        renderer.draw(iter::once(triangle)).expect("couldn't draw");
+        renderer.swap();
+        renderer.present(&mut window).expect("couldn't present");
+
+        angle += 0.02;
+    }
+}
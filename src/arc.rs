@@ -0,0 +1,264 @@
+//! Parts of circles bounded by an angular range.
+//!
+//! Angles are in radians and grow from the positive x axis towards the
+//! positive y axis, i.e. counter-clockwise in a y-up coordinate system. The
+//! range sweeps from `start_angle` to `end_angle` in that direction, and
+//! wraps around if `start_angle > end_angle`.
+
+use std::f64::consts::PI;
+
+use num_traits::{AsPrimitive, Float};
+
+use renderer::Drawable;
+use point::Point2;
+use rect::{self, Rectangle};
+
+/// Primitive drawable outline of a circle within an angular range.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Arc<T> {
+    center: Point2<T>,
+    radius: T,
+    start_angle: T,
+    end_angle: T,
+}
+
+impl<T> Arc<T> {
+    #[inline(always)]
+    pub fn new(center: Point2<T>, radius: T, start_angle: T, end_angle: T) -> Self {
+        Arc {
+            center,
+            radius,
+            start_angle,
+            end_angle,
+        }
+    }
+}
+
+/// Primitive drawable filled wedge of a circle, a.k.a. a pie slice.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Sector<T> {
+    center: Point2<T>,
+    radius: T,
+    start_angle: T,
+    end_angle: T,
+}
+
+impl<T> Sector<T> {
+    #[inline(always)]
+    pub fn new(center: Point2<T>, radius: T, start_angle: T, end_angle: T) -> Self {
+        Sector {
+            center,
+            radius,
+            start_angle,
+            end_angle,
+        }
+    }
+}
+
+impl<T: Float + AsPrimitive<i64> + 'static> Drawable<T, Point2<T>> for Arc<T>
+where
+    i64: AsPrimitive<T>,
+{
+    #[inline(always)]
+    fn vertices(&self) -> usize {
+        1
+    }
+}
+
+impl<T: Float + AsPrimitive<i64> + 'static> Drawable<T, Point2<T>> for Sector<T>
+where
+    i64: AsPrimitive<T>,
+{
+    #[inline(always)]
+    fn vertices(&self) -> usize {
+        1
+    }
+}
+
+impl<T: Float + AsPrimitive<i64> + 'static> IntoIterator for Arc<T>
+where
+    i64: AsPrimitive<T>,
+{
+    type Item = Point2<T>;
+    type IntoIter = IntoIter<T>;
+
+    /// Iterate over the pixels of the outline within the angular range,
+    /// i.e. those less than half a pixel off the circle.
+    ///
+    /// An arc with a negative radius yields nothing.
+    #[inline]
+    fn into_iter(self) -> Self::IntoIter {
+        let half = T::from(0.5).unwrap();
+        let inner = (self.radius - half).max(T::zero());
+        let outer = self.radius + half;
+
+        IntoIter::new(
+            self.center,
+            self.radius,
+            (inner * inner, outer * outer),
+            (self.start_angle, self.end_angle),
+        )
+    }
+}
+
+impl<T: Float + AsPrimitive<i64> + 'static> IntoIterator for Sector<T>
+where
+    i64: AsPrimitive<T>,
+{
+    type Item = Point2<T>;
+    type IntoIter = IntoIter<T>;
+
+    /// Iterate over all the pixels inside the wedge. A full turn yields the
+    /// same pixels as a `Circle`.
+    ///
+    /// A sector with a negative radius yields nothing.
+    #[inline]
+    fn into_iter(self) -> Self::IntoIter {
+        let r2 = self.radius * self.radius;
+
+        IntoIter::new(
+            self.center,
+            self.radius,
+            (T::zero(), r2),
+            (self.start_angle, self.end_angle),
+        )
+    }
+}
+
+#[derive(Debug)]
+pub struct IntoIter<T> {
+    rect: rect::IntoIter<T>,
+    center: Point2<T>,
+    /// The range of the squared distance from `center`, inclusive.
+    r2: (T, T),
+    /// The normalized angular range, or `None` for a full turn.
+    angles: Option<(T, T)>,
+}
+
+impl<T: Float + AsPrimitive<i64> + 'static> IntoIter<T>
+where
+    i64: AsPrimitive<T>,
+{
+    #[inline]
+    fn new(center: Point2<T>, radius: T, r2: (T, T), (start, end): (T, T)) -> Self {
+        let (cx, cy) = center;
+        let rect = if radius < T::zero() {
+            Rectangle::new(T::zero(), T::zero(), T::zero(), T::zero())
+        } else {
+            let r = radius + T::from(0.5).unwrap();
+            let x0 = (cx - r).floor();
+            let x1 = (cx + r).floor() + T::one();
+            let y0 = (cy - r).floor();
+            let y1 = (cy + r).floor() + T::one();
+            Rectangle::new(x0, x1, y0, y1)
+        };
+
+        let tau = T::from(2.0 * PI).unwrap();
+        let angles = if (end - start).abs() >= tau {
+            None
+        } else {
+            Some((normalize(start), normalize(end)))
+        };
+
+        IntoIter {
+            rect: rect.into_iter(),
+            center,
+            r2,
+            angles,
+        }
+    }
+}
+
+/// Map the angle `a` into `[0, 2π)`.
+#[inline(always)]
+fn normalize<T: Float>(a: T) -> T {
+    let tau = T::from(2.0 * PI).unwrap();
+    let a = a % tau;
+    if a < T::zero() {
+        a + tau
+    } else {
+        a
+    }
+}
+
+impl<T: Float + 'static> Iterator for IntoIter<T>
+where
+    i64: AsPrimitive<T>,
+{
+    type Item = Point2<T>;
+
+    #[inline]
+    fn next(&mut self) -> Option<Self::Item> {
+        let (cx, cy) = self.center;
+        let (min, max) = self.r2;
+        let angles = self.angles;
+
+        self.rect.find(|&(x, y)| {
+            let dx = x - cx;
+            let dy = y - cy;
+            let d2 = dx * dx + dy * dy;
+            if d2 < min || d2 > max {
+                return false;
+            }
+
+            match angles {
+                None => true,
+                Some((start, end)) => {
+                    let a = normalize(dy.atan2(dx));
+                    if start <= end {
+                        start <= a && a <= end
+                    } else {
+                        a >= start || a <= end
+                    }
+                }
+            }
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::BTreeSet;
+    use std::f64::consts::PI;
+
+    use circle::Circle;
+    use super::{Arc, Sector};
+
+    #[test]
+    fn quarter_arc() {
+        let pixels = Arc::new((10.0, 10.0), 5.0, 0.0, PI / 2.0)
+            .into_iter()
+            .map(|(x, y)| (x as i64, y as i64))
+            .collect::<BTreeSet<_>>();
+
+        assert!(pixels.contains(&(15, 10)));
+        assert!(pixels.contains(&(10, 15)));
+        assert!(pixels.iter().all(|&(x, y)| x >= 10 && y >= 10));
+        // only the outline, not the inside
+        assert!(!pixels.contains(&(12, 12)));
+
+        // wrapping around from the 4th to the 1st quadrant
+        let pixels = Arc::new((10.0, 10.0), 5.0, 1.5 * PI, PI / 2.0)
+            .into_iter()
+            .map(|(x, y)| (x as i64, y as i64))
+            .collect::<BTreeSet<_>>();
+        assert!(pixels.contains(&(10, 5)));
+        assert!(pixels.contains(&(15, 10)));
+        assert!(pixels.iter().all(|&(x, _)| x >= 10));
+    }
+
+    #[test]
+    fn full_sector() {
+        let sector = Sector::new((5.5, 6.0), 4.0, 0.0, 2.0 * PI)
+            .into_iter()
+            .collect::<Vec<_>>();
+        let disc = Circle::new((5.5, 6.0), 4.0).into_iter().collect::<Vec<_>>();
+        assert_eq!(sector, disc);
+
+        let half = Sector::new((5.0, 5.0), 3.0, PI, 2.0 * PI)
+            .into_iter()
+            .collect::<Vec<_>>();
+        assert!(half.iter().all(|&(_, y)| y <= 5.0));
+        assert!(half.contains(&(5.0, 2.0)));
+    }
+}
@@ -0,0 +1,252 @@
+use std::fmt::{self, Debug};
+use std::vec;
+
+use line_drawing::{FloatNum, Midpoint};
+use num_traits::AsPrimitive;
+
+use renderer::Drawable;
+use point::Point2;
+
+/// The maximum distance in pixels between a curve and the polyline it's
+/// rasterized as.
+const FLATNESS: f64 = 0.25;
+/// The maximum recursion depth of the subdivision, for curves with
+/// non-finite control points.
+const MAX_DEPTH: usize = 16;
+
+/// Primitive drawable quadratic Bézier curve.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct QuadBezier<T> {
+    p0: Point2<T>,
+    p1: Point2<T>,
+    p2: Point2<T>,
+}
+
+impl<T> QuadBezier<T> {
+    #[inline(always)]
+    pub fn new(p0: Point2<T>, p1: Point2<T>, p2: Point2<T>) -> Self {
+        QuadBezier { p0, p1, p2 }
+    }
+}
+
+impl<T: FloatNum> QuadBezier<T> {
+    /// Get the equivalent cubic Bézier curve.
+    #[inline]
+    pub fn to_cubic(&self) -> CubicBezier<T> {
+        let two_thirds = T::from(2.0 / 3.0).unwrap();
+        let lerp = |a: Point2<T>, b: Point2<T>| {
+            (a.0 + (b.0 - a.0) * two_thirds, a.1 + (b.1 - a.1) * two_thirds)
+        };
+
+        CubicBezier::new(
+            self.p0,
+            lerp(self.p0, self.p1),
+            lerp(self.p2, self.p1),
+            self.p2,
+        )
+    }
+}
+
+impl<T: FloatNum + 'static> Drawable<T, Point2<T>> for QuadBezier<T>
+where
+    i64: AsPrimitive<T>,
+{
+    #[inline(always)]
+    fn vertices(&self) -> usize {
+        3
+    }
+}
+
+impl<T: FloatNum + 'static> IntoIterator for QuadBezier<T>
+where
+    i64: AsPrimitive<T>,
+{
+    type Item = Point2<T>;
+    type IntoIter = IntoIter<T>;
+
+    /// Iterate over the pixels of the curve. See `CubicBezier::into_iter`.
+    #[inline]
+    fn into_iter(self) -> Self::IntoIter {
+        self.to_cubic().into_iter()
+    }
+}
+
+/// Primitive drawable cubic Bézier curve.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct CubicBezier<T> {
+    p0: Point2<T>,
+    p1: Point2<T>,
+    p2: Point2<T>,
+    p3: Point2<T>,
+}
+
+impl<T> CubicBezier<T> {
+    #[inline(always)]
+    pub fn new(p0: Point2<T>, p1: Point2<T>, p2: Point2<T>, p3: Point2<T>) -> Self {
+        CubicBezier { p0, p1, p2, p3 }
+    }
+}
+
+impl<T: FloatNum + 'static> Drawable<T, Point2<T>> for CubicBezier<T>
+where
+    i64: AsPrimitive<T>,
+{
+    #[inline(always)]
+    fn vertices(&self) -> usize {
+        4
+    }
+}
+
+impl<T: FloatNum + 'static> IntoIterator for CubicBezier<T>
+where
+    i64: AsPrimitive<T>,
+{
+    type Item = Point2<T>;
+    type IntoIter = IntoIter<T>;
+
+    /// Iterate over the pixels of the curve.
+    ///
+    /// The curve is subdivided until every piece is within `FLATNESS` of a
+    /// straight segment, so the number of segments grows with the length and
+    /// the curvature of the curve. The segments are then rasterized like a
+    /// `Line`. Pixels shared by consecutive segments are yielded once.
+    #[inline]
+    fn into_iter(self) -> Self::IntoIter {
+        let mut points = Vec::new();
+        flatten([self.p0, self.p1, self.p2, self.p3], 0, &mut points);
+
+        IntoIter {
+            start: self.p0,
+            points: points.into_iter(),
+            segment: None,
+            last: None,
+        }
+    }
+}
+
+/// Push the end points of the straight segments approximating the cubic
+/// curve `p` to `out`, excluding its start point.
+fn flatten<T: FloatNum>(p: [Point2<T>; 4], depth: usize, out: &mut Vec<Point2<T>>) {
+    let (dx, dy) = (p[3].0 - p[0].0, p[3].1 - p[0].1);
+    let len2 = dx * dx + dy * dy;
+    let tolerance = T::from(FLATNESS * FLATNESS).unwrap();
+
+    // squared distance of a control point from the chord, or from the start
+    // point if the chord is degenerate
+    let dist2 = |q: Point2<T>| {
+        let (qx, qy) = (q.0 - p[0].0, q.1 - p[0].1);
+        if len2 == T::zero() {
+            qx * qx + qy * qy
+        } else {
+            let cross = dx * qy - dy * qx;
+            cross * cross / len2
+        }
+    };
+
+    if depth >= MAX_DEPTH || (dist2(p[1]) <= tolerance && dist2(p[2]) <= tolerance) {
+        out.push(p[3]);
+        return;
+    }
+
+    // de Casteljau at t = 0.5
+    let half = T::from(0.5).unwrap();
+    let mid = |a: Point2<T>, b: Point2<T>| ((a.0 + b.0) * half, (a.1 + b.1) * half);
+    let p01 = mid(p[0], p[1]);
+    let p12 = mid(p[1], p[2]);
+    let p23 = mid(p[2], p[3]);
+    let p012 = mid(p01, p12);
+    let p123 = mid(p12, p23);
+    let p0123 = mid(p012, p123);
+
+    flatten([p[0], p01, p012, p0123], depth + 1, out);
+    flatten([p0123, p123, p23, p[3]], depth + 1, out);
+}
+
+pub struct IntoIter<T: FloatNum> {
+    start: Point2<T>,
+    points: vec::IntoIter<Point2<T>>,
+    segment: Option<Midpoint<T, i64>>,
+    last: Option<Point2<i64>>,
+}
+
+impl<T: FloatNum + 'static> Iterator for IntoIter<T>
+where
+    i64: AsPrimitive<T>,
+{
+    type Item = Point2<T>;
+
+    #[inline]
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            if let Some(p) = self.segment.as_mut().and_then(|segment| segment.next()) {
+                if self.last != Some(p) {
+                    self.last = Some(p);
+                    return Some((p.0.as_(), p.1.as_()));
+                }
+                continue;
+            }
+
+            let end = self.points.next()?;
+            self.segment = Some(Midpoint::new(self.start, end));
+            self.start = end;
+        }
+    }
+}
+
+impl<T: FloatNum + Debug> Debug for IntoIter<T> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("IntoIter")
+            .field("start", &self.start)
+            .field("points", &self.points)
+            .field("segment", &"...")
+            .field("last", &self.last)
+            .finish()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{CubicBezier, QuadBezier};
+
+    fn pixels<I: IntoIterator<Item = (f64, f64)>>(curve: I) -> Vec<(i64, i64)> {
+        curve
+            .into_iter()
+            .map(|(x, y)| (x as i64, y as i64))
+            .collect()
+    }
+
+    #[test]
+    fn endpoints() {
+        let curves = [
+            CubicBezier::new((2.0, 3.0), (40.0, -20.0), (-10.0, 50.0), (30.0, 25.0)),
+            CubicBezier::new((5.0, 5.0), (5.0, 5.0), (5.0, 5.0), (5.0, 5.0)),
+            CubicBezier::new((1.0, 1.0), (2.0, 0.0), (3.0, 2.0), (1.0, 1.0)),
+            QuadBezier::new((0.0, 0.0), (500.0, 1000.0), (1000.0, 0.0)).to_cubic(),
+        ];
+
+        for curve in &curves {
+            let pixels = pixels(*curve);
+            assert_eq!(pixels[0], (curve.p0.0 as i64, curve.p0.1 as i64));
+            assert_eq!(
+                *pixels.last().unwrap(),
+                (curve.p3.0 as i64, curve.p3.1 as i64)
+            );
+
+            // no gaps, even on long curves
+            for pair in pixels.windows(2) {
+                assert!((pair[0].0 - pair[1].0).abs() <= 1);
+                assert!((pair[0].1 - pair[1].1).abs() <= 1);
+            }
+        }
+    }
+
+    #[test]
+    fn nearly_straight() {
+        let curve = CubicBezier::new((0.0, 10.0), (10.0, 10.2), (20.0, 9.8), (30.0, 10.0));
+        let expected = (0..31).map(|x| (x, 10)).collect::<Vec<_>>();
+        assert_eq!(pixels(curve), expected);
+
+        let curve = QuadBezier::new((0.0, 10.0), (15.0, 10.1), (30.0, 10.0));
+        assert_eq!(pixels(curve), expected);
+    }
+}
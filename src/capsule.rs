@@ -0,0 +1,185 @@
+use num_traits::{AsPrimitive, Float};
+
+use renderer::Drawable;
+use point::Point2;
+use rect::{self, Rectangle};
+use circle::{self, Circle};
+
+/// Primitive drawable filled capsule, a.k.a. a stadium, i.e. all the points
+/// within `radius` of the segment between `a` and `b`.
+#[derive(Clone, Copy, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct Capsule<T> {
+    a: Point2<T>,
+    b: Point2<T>,
+    radius: T,
+}
+
+impl<T> Capsule<T> {
+    #[inline(always)]
+    pub fn new(a: Point2<T>, b: Point2<T>, radius: T) -> Self {
+        Capsule { a, b, radius }
+    }
+}
+
+impl<T: Float> Capsule<T> {
+    /// Get the smallest `Rectangle` containing the capsule.
+    #[inline]
+    pub fn bounding_box(&self) -> Rectangle<T> {
+        let r = self.radius.abs();
+        Rectangle::new(
+            self.a.0.min(self.b.0) - r,
+            self.a.0.max(self.b.0) + r,
+            self.a.1.min(self.b.1) - r,
+            self.a.1.max(self.b.1) + r,
+        )
+    }
+}
+
+impl<T: Float + AsPrimitive<i64> + 'static> Drawable<T, Point2<T>> for Capsule<T>
+where
+    i64: AsPrimitive<T>,
+{
+    #[inline(always)]
+    fn vertices(&self) -> usize {
+        2
+    }
+}
+
+impl<T: Float + AsPrimitive<i64> + 'static> IntoIterator for Capsule<T>
+where
+    i64: AsPrimitive<T>,
+{
+    type Item = Point2<T>;
+    type IntoIter = IntoIter<T>;
+
+    /// Iterate over all the pixels within `radius` of the segment.
+    ///
+    /// A capsule with `a == b` is drawn like a `Circle` around `a`, a
+    /// capsule with a negative radius yields nothing.
+    #[inline]
+    fn into_iter(self) -> Self::IntoIter {
+        if self.a == self.b {
+            let circle = Circle::new(self.a, self.radius).into_iter();
+            return IntoIter {
+                inner: Inner::Circle(circle),
+            };
+        }
+
+        let r = self.radius;
+        let rect = if r < T::zero() {
+            Rectangle::new(T::zero(), T::zero(), T::zero(), T::zero())
+        } else {
+            let (a, b) = (self.a, self.b);
+            let x0 = (a.0.min(b.0) - r).floor();
+            let x1 = (a.0.max(b.0) + r).floor() + T::one();
+            let y0 = (a.1.min(b.1) - r).floor();
+            let y1 = (a.1.max(b.1) + r).floor() + T::one();
+            Rectangle::new(x0, x1, y0, y1)
+        };
+
+        IntoIter {
+            inner: Inner::Capsule {
+                rect: rect.into_iter(),
+                a: self.a,
+                b: self.b,
+                r2: r * r,
+            },
+        }
+    }
+}
+
+#[derive(Debug)]
+pub struct IntoIter<T> {
+    inner: Inner<T>,
+}
+
+/// The pixels of either a proper or a degenerate capsule.
+#[derive(Debug)]
+enum Inner<T> {
+    Circle(circle::IntoIter<T>),
+    Capsule {
+        rect: rect::IntoIter<T>,
+        a: Point2<T>,
+        b: Point2<T>,
+        r2: T,
+    },
+}
+
+impl<T: Float + 'static> Iterator for IntoIter<T>
+where
+    i64: AsPrimitive<T>,
+{
+    type Item = Point2<T>;
+
+    #[inline]
+    fn next(&mut self) -> Option<Self::Item> {
+        match self.inner {
+            Inner::Circle(ref mut circle) => circle.next(),
+            Inner::Capsule {
+                ref mut rect,
+                a,
+                b,
+                r2,
+            } => {
+                let (dx, dy) = (b.0 - a.0, b.1 - a.1);
+                let len2_recip = (dx * dx + dy * dy).recip();
+
+                rect.find(|&(x, y)| {
+                    // the nearest point on the segment
+                    let (px, py) = (x - a.0, y - a.1);
+                    let t = ((px * dx + py * dy) * len2_recip)
+                        .max(T::zero())
+                        .min(T::one());
+                    let (ex, ey) = (px - dx * t, py - dy * t);
+                    ex * ex + ey * ey <= r2
+                })
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::f64::consts::PI;
+
+    use circle::Circle;
+    use super::Capsule;
+
+    #[test]
+    fn capsule() {
+        // off the pixel grid, so that few pixels lie exactly on the edge
+        let pixels = Capsule::new((5.5, 5.5), (15.5, 5.5), 3.0)
+            .into_iter()
+            .map(|(x, y)| (x as i64, y as i64))
+            .collect::<Vec<_>>();
+
+        // the band along the segment
+        assert!(pixels.contains(&(10, 3)));
+        assert!(pixels.contains(&(10, 8)));
+        assert!(!pixels.contains(&(10, 9)));
+        // the rounded caps, but not the corners of the bounding box
+        assert!(pixels.contains(&(3, 5)));
+        assert!(pixels.contains(&(18, 5)));
+        assert!(!pixels.contains(&(3, 3)));
+        assert!(!pixels.contains(&(18, 8)));
+
+        let area = 10.0 * 6.0 + PI * 9.0;
+        assert!((pixels.len() as f64 - area).abs() < area * 0.1);
+    }
+
+    #[test]
+    fn degenerate() {
+        let capsule = Capsule::new((5.0, 5.0), (5.0, 5.0), 2.0);
+        let circle = Circle::new((5.0, 5.0), 2.0);
+        assert_eq!(
+            capsule.into_iter().collect::<Vec<_>>(),
+            circle.into_iter().collect::<Vec<_>>()
+        );
+        assert_eq!(
+            Capsule::new((5.5, 5.5), (5.5, 5.5), 0.0).into_iter().collect::<Vec<_>>(),
+            [(5.0, 5.0)]
+        );
+        assert_eq!(Capsule::new((0.0, 0.0), (4.0, 0.0), -1.0).into_iter().count(), 0);
+    }
+}
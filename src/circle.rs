@@ -0,0 +1,277 @@
+use num_traits::{AsPrimitive, Float};
+
+use renderer::Drawable;
+use point::Point2;
+use rect::{self, Rectangle};
+
+/// Primitive drawable filled circle, a.k.a. a disc.
+#[derive(Clone, Copy, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct Circle<T> {
+    center: Point2<T>,
+    radius: T,
+}
+
+impl<T> Circle<T> {
+    #[inline(always)]
+    pub fn new(center: Point2<T>, radius: T) -> Self {
+        Circle { center, radius }
+    }
+}
+
+impl<T: Float> Circle<T> {
+    /// Get the smallest `Rectangle` containing the circle.
+    #[inline]
+    pub fn bounding_box(&self) -> Rectangle<T> {
+        let (cx, cy) = self.center;
+        let r = self.radius.abs();
+        Rectangle::new(cx - r, cx + r, cy - r, cy + r)
+    }
+}
+
+impl<T: Float + AsPrimitive<i64> + 'static> Circle<T>
+where
+    i64: AsPrimitive<T>,
+{
+    /// Iterate over all the pixels covered by the circle, together with
+    /// their approximate coverage in `(0, 1]`, i.e. for anti-aliased edges.
+    ///
+    /// Every pixel is the unit square centered on its coordinate. Pixels
+    /// entirely inside the circle have a coverage of exactly 1, only pixels
+    /// straddling the edge are subsampled 4 by 4 times. Pixels without any
+    /// coverage, and so circles with a radius of zero or less, yield
+    /// nothing.
+    #[inline]
+    pub fn into_iter_aa(self) -> AaIter<T> {
+        let (cx, cy) = self.center;
+        let r = self.radius;
+        let half = T::from(0.5).unwrap();
+
+        let rect = if r <= T::zero() {
+            Rectangle::new(T::zero(), T::zero(), T::zero(), T::zero())
+        } else {
+            let x0 = (cx - r - half).floor();
+            let x1 = (cx + r + half).floor() + T::one();
+            let y0 = (cy - r - half).floor();
+            let y1 = (cy + r + half).floor() + T::one();
+            Rectangle::new(x0, x1, y0, y1)
+        };
+
+        AaIter {
+            rect: rect.into_iter(),
+            center: self.center,
+            r2: r * r,
+        }
+    }
+}
+
+impl<T: Float + AsPrimitive<i64> + 'static> Drawable<T, Point2<T>> for Circle<T>
+where
+    i64: AsPrimitive<T>,
+{
+    #[inline(always)]
+    fn vertices(&self) -> usize {
+        1
+    }
+}
+
+impl<T: Float + AsPrimitive<i64> + 'static> IntoIterator for Circle<T>
+where
+    i64: AsPrimitive<T>,
+{
+    type Item = Point2<T>;
+    type IntoIter = IntoIter<T>;
+
+    /// Iterate over all the pixels inside the circle.
+    ///
+    /// A circle with a radius of zero yields only the pixel containing its
+    /// center, a circle with a negative radius yields nothing.
+    #[inline]
+    fn into_iter(self) -> Self::IntoIter {
+        let (cx, cy) = self.center;
+        let r = self.radius;
+
+        let (rect, r2) = if r < T::zero() {
+            let rect = Rectangle::new(T::zero(), T::zero(), T::zero(), T::zero());
+            (rect, r)
+        } else if r == T::zero() {
+            // the bounds contain only the center pixel, so skip the
+            // distance test, which might fail for a fractional center
+            let (x, y) = (cx.floor(), cy.floor());
+            let rect = Rectangle::new(x, x + T::one(), y, y + T::one());
+            (rect, T::infinity())
+        } else {
+            let x0 = (cx - r).floor();
+            let x1 = (cx + r).floor() + T::one();
+            let y0 = (cy - r).floor();
+            let y1 = (cy + r).floor() + T::one();
+            (Rectangle::new(x0, x1, y0, y1), r * r)
+        };
+
+        IntoIter {
+            rect: rect.into_iter(),
+            center: self.center,
+            r2,
+        }
+    }
+}
+
+#[derive(Debug)]
+pub struct IntoIter<T> {
+    rect: rect::IntoIter<T>,
+    center: Point2<T>,
+    r2: T,
+}
+
+impl<T: Float + 'static> Iterator for IntoIter<T>
+where
+    i64: AsPrimitive<T>,
+{
+    type Item = Point2<T>;
+
+    #[inline]
+    fn next(&mut self) -> Option<Self::Item> {
+        let (cx, cy) = self.center;
+        let r2 = self.r2;
+
+        self.rect.find(|&(x, y)| {
+            let dx = x - cx;
+            let dy = y - cy;
+            dx * dx + dy * dy <= r2
+        })
+    }
+}
+
+/// An `Iterator` over the pixels of a `Circle` and their coverage.
+///
+/// See `Circle::into_iter_aa`.
+#[derive(Debug)]
+pub struct AaIter<T> {
+    rect: rect::IntoIter<T>,
+    center: Point2<T>,
+    r2: T,
+}
+
+impl<T: Float + 'static> AaIter<T> {
+    /// Count how many of the 4 by 4 subsamples of the pixel at `(x, y)` lie
+    /// inside the circle.
+    #[inline]
+    fn subsamples(&self, (x, y): Point2<T>) -> usize {
+        let (cx, cy) = self.center;
+        let offset = |i: usize| T::from(i as f64 * 0.25 - 0.375).unwrap();
+
+        let mut inside = 0;
+        for i in 0..4 {
+            let dy = y + offset(i) - cy;
+            for j in 0..4 {
+                let dx = x + offset(j) - cx;
+                if dx * dx + dy * dy <= self.r2 {
+                    inside += 1;
+                }
+            }
+        }
+        inside
+    }
+}
+
+impl<T: Float + 'static> Iterator for AaIter<T>
+where
+    i64: AsPrimitive<T>,
+{
+    type Item = (Point2<T>, T);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let (cx, cy) = self.center;
+        let half = T::from(0.5).unwrap();
+
+        while let Some((x, y)) = self.rect.next() {
+            let (dx, dy) = ((x - cx).abs(), (y - cy).abs());
+
+            // the farthest corner of the pixel is inside
+            let (fx, fy) = (dx + half, dy + half);
+            if fx * fx + fy * fy <= self.r2 {
+                return Some(((x, y), T::one()));
+            }
+
+            // the nearest point of the pixel is outside
+            let (nx, ny) = ((dx - half).max(T::zero()), (dy - half).max(T::zero()));
+            if nx * nx + ny * ny >= self.r2 {
+                continue;
+            }
+
+            let inside = self.subsamples((x, y));
+            if inside > 0 {
+                return Some(((x, y), T::from(inside).unwrap() / T::from(16).unwrap()));
+            }
+        }
+
+        None
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::BTreeSet;
+    use std::f64::consts::PI;
+
+    use super::Circle;
+
+    #[test]
+    fn circle() {
+        let pixels = Circle::new((5.0, 5.0), 2.0)
+            .into_iter()
+            .map(|(x, y)| (x as i64, y as i64))
+            .collect::<BTreeSet<_>>();
+        let expected = [
+            (5, 3),
+            (4, 4),
+            (5, 4),
+            (6, 4),
+            (3, 5),
+            (4, 5),
+            (5, 5),
+            (6, 5),
+            (7, 5),
+            (4, 6),
+            (5, 6),
+            (6, 6),
+            (5, 7),
+        ]
+        .iter()
+        .cloned()
+        .collect::<BTreeSet<_>>();
+
+        assert_eq!(pixels, expected);
+    }
+
+    #[test]
+    fn degenerate() {
+        assert_eq!(
+            Circle::new((5.0, 5.0), 0.0).into_iter().collect::<Vec<_>>(),
+            [(5.0, 5.0)]
+        );
+        assert_eq!(
+            Circle::new((5.5, 5.5), 0.0).into_iter().collect::<Vec<_>>(),
+            [(5.0, 5.0)]
+        );
+        assert_eq!(Circle::new((5.0, 5.0), -1.0).into_iter().count(), 0);
+    }
+
+    #[test]
+    fn aa() {
+        let circle = Circle::new((5.0, 5.0), 2.0);
+        let pixels = circle.into_iter_aa().collect::<Vec<_>>();
+
+        let coverage = |p| pixels.iter().find(|px| px.0 == p).map(|px| px.1);
+        assert_eq!(coverage((5.0, 5.0)), Some(1.0));
+        let edge = coverage((7.0, 5.0)).unwrap();
+        assert!(edge > 0.0 && edge < 1.0);
+        assert_eq!(coverage((8.0, 5.0)), None);
+
+        let area: f64 = pixels.iter().map(|px| px.1).sum();
+        assert!((area - PI * 4.0).abs() < 0.5);
+
+        assert_eq!(Circle::new((5.0, 5.0), 0.0).into_iter_aa().count(), 0);
+        assert_eq!(Circle::new((5.0, 5.0), -1.0).into_iter_aa().count(), 0);
+    }
+}
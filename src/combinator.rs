@@ -0,0 +1,126 @@
+//! Adapters over the coordinates of `Drawable`-s.
+
+use std::ops::Add;
+
+use renderer::{Coord, Drawable};
+use point::Point2;
+
+/// A coordinate offset by a translation, yielded by `Translated`.
+///
+/// The barycentric point and the depth are kept as they are.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct TranslatedCoord<C, T> {
+    coord: C,
+    offset: Point2<T>,
+}
+
+impl<C, T> TranslatedCoord<C, T> {
+    /// Get the original, untranslated coordinate.
+    #[inline(always)]
+    pub fn coord(&self) -> &C {
+        &self.coord
+    }
+}
+
+impl<C: Coord<T>, T: Copy + Add<Output = T>> Coord<T> for TranslatedCoord<C, T> {
+    #[inline(always)]
+    fn point(&self) -> Point2<T> {
+        let (x, y) = self.coord.point();
+        (x + self.offset.0, y + self.offset.1)
+    }
+
+    #[inline(always)]
+    fn barycentric(&self) -> Option<&[T]> {
+        self.coord.barycentric()
+    }
+
+    #[inline(always)]
+    fn depth(&self) -> Option<T> {
+        self.coord.depth()
+    }
+}
+
+/// An `Iterator` over the coordinates of a `Drawable`, offset by a
+/// translation, which can be drawn itself.
+///
+/// See `Translate::translate`.
+#[derive(Clone, Debug)]
+pub struct Translated<I, T> {
+    vertices: usize,
+    offset: Point2<T>,
+    inner: I,
+}
+
+impl<I: Iterator, T: Copy> Iterator for Translated<I, T> {
+    type Item = TranslatedCoord<I::Item, T>;
+
+    #[inline]
+    fn next(&mut self) -> Option<Self::Item> {
+        let offset = self.offset;
+        self.inner
+            .next()
+            .map(|coord| TranslatedCoord { coord, offset })
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.inner.size_hint()
+    }
+}
+
+impl<C, I, T> Drawable<T, TranslatedCoord<C, T>> for Translated<I, T>
+where
+    C: Coord<T>,
+    I: Iterator<Item = C>,
+    T: Copy + Add<Output = T>,
+{
+    #[inline(always)]
+    fn vertices(&self) -> usize {
+        self.vertices
+    }
+}
+
+/// An extension trait for offsetting every fragment of a `Drawable`.
+pub trait Translate<T, C: Coord<T>>: Drawable<T, C> + Sized {
+    /// Offset every coordinate of `self` by `(dx, dy)`.
+    ///
+    /// Unlike `Transform::transform`, this doesn't rebuild the primitive, so
+    /// drawing one primitive at many positions rasterizes the same fragments
+    /// each time.
+    #[inline]
+    fn translate(self, dx: T, dy: T) -> Translated<Self::IntoIter, T> {
+        Translated {
+            vertices: self.vertices(),
+            offset: (dx, dy),
+            inner: self.into_iter(),
+        }
+    }
+}
+
+impl<T, C: Coord<T>, D: Drawable<T, C>> Translate<T, C> for D {}
+
+#[cfg(test)]
+mod tests {
+    use renderer::{Coord, Drawable};
+    use tri::Triangle;
+    use super::Translate;
+
+    #[test]
+    fn translate_triangle() {
+        let triangle = Triangle::with_points([(1.0_f64, 1.0), (9.0, 2.0), (3.0, 8.0)]);
+        let shifted = Triangle::with_points([(6.0, 6.0), (14.0, 7.0), (8.0, 13.0)]);
+
+        let translated = triangle.translate(5.0, 5.0);
+        assert_eq!(translated.vertices(), 3);
+
+        let translated: Vec<_> = translated.collect();
+        let expected: Vec<_> = shifted.into_iter().collect();
+        assert_eq!(translated.len(), expected.len());
+        for (c, e) in translated.iter().zip(&expected) {
+            assert_eq!(c.point(), e.point());
+            for (a, b) in c.barycentric().unwrap().iter().zip(&e.1) {
+                assert!((a - b).abs() < 1e-9);
+            }
+        }
+    }
+}
@@ -0,0 +1,208 @@
+//! A list of primitives drawn in depth order.
+
+use std::cmp::Ordering;
+use std::iter;
+
+use line_drawing::FloatNum;
+use num_traits::{AsPrimitive, Float, Signed};
+
+use renderer::{Renderer, Stats};
+use shape::Shape;
+
+/// The order in which a `DrawList` draws its primitives. Smaller depths are
+/// nearer to the viewer.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum SortOrder {
+    /// Draw the farthest primitive first, i.e. for translucent primitives
+    /// blended with `BlendMode::Over`.
+    BackToFront,
+    /// Draw the nearest primitive first, i.e. so that the depth test
+    /// discards most of the hidden fragments early.
+    FrontToBack,
+}
+
+/// A batch of `Shape`-s with a depth each, drawn sorted by depth, so that
+/// the order they're pushed in doesn't matter.
+///
+/// # Parameters
+///
+/// - `T` represents the primitive numeric type used in base computation.
+/// - `A` represents the attribute of the `Renderer` it's flushed to.
+#[derive(Clone, Debug)]
+pub struct DrawList<T, A> {
+    order: SortOrder,
+    items: Vec<(Shape<T>, T, Option<A>)>,
+}
+
+impl<T, A> DrawList<T, A> {
+    /// Create an empty `DrawList` drawing in `order`.
+    #[inline(always)]
+    pub fn new(order: SortOrder) -> Self {
+        DrawList {
+            order,
+            items: Vec::new(),
+        }
+    }
+
+    /// Get the count of primitives waiting to be drawn.
+    #[inline(always)]
+    pub fn len(&self) -> usize {
+        self.items.len()
+    }
+
+    /// Whether there are no primitives waiting to be drawn.
+    #[inline(always)]
+    pub fn is_empty(&self) -> bool {
+        self.items.is_empty()
+    }
+
+    /// Add `shape` at `depth`, drawn with the attribute the renderer has at
+    /// the time of the `flush`.
+    #[inline]
+    pub fn push<S: Into<Shape<T>>>(&mut self, shape: S, depth: T) {
+        self.items.push((shape.into(), depth, None));
+    }
+
+    /// Add `shape` at `depth`, drawn with `attr` as the 0th attribute, i.e.
+    /// its color.
+    #[inline]
+    pub fn push_with_attr<S: Into<Shape<T>>>(&mut self, shape: S, depth: T, attr: A) {
+        self.items.push((shape.into(), depth, Some(attr)));
+    }
+}
+
+impl<T, A> DrawList<T, A>
+where
+    T: Float + FloatNum + Signed + AsPrimitive<i64> + AsPrimitive<usize> + 'static,
+    i64: AsPrimitive<T>,
+{
+    /// Sort the primitives by depth and draw them with `renderer`, leaving
+    /// the list empty. Primitives of equal depth are drawn in the order
+    /// they were pushed.
+    ///
+    /// The attributes of `renderer` are left untouched, so primitives
+    /// pushed without an attribute aren't drawn if it has none.
+    ///
+    /// # Returns
+    ///
+    /// - `Ok(Stats)` summed over all the primitives.
+    /// - `Err(R::Error)` of the first failed draw. The remaining primitives
+    ///   are dropped.
+    pub fn flush<R>(&mut self, renderer: &mut R) -> Result<Stats, R::Error>
+    where
+        R: Renderer<T, Attr = A>,
+        R::Pixel: Clone,
+        A: Into<R::Pixel>,
+    {
+        let order = self.order;
+        self.items.sort_by(|a, b| {
+            let ord = a.1.partial_cmp(&b.1).unwrap_or(Ordering::Equal);
+            match order {
+                SortOrder::BackToFront => ord.reverse(),
+                SortOrder::FrontToBack => ord,
+            }
+        });
+
+        let mut total = Stats::default();
+        let mut result = Ok(());
+        for (shape, _, attr) in self.items.drain(..) {
+            let stats = match attr {
+                Some(attr) => {
+                    // like `draw` with `attr` set
+                    let px = renderer.clamp_attr(attr).into();
+                    renderer.draw_with(iter::once(shape), move |_| Some(px.clone()))
+                }
+                None => renderer.draw(iter::once(shape)),
+            };
+
+            match stats {
+                Ok(stats) => {
+                    total.shapes += stats.shapes;
+                    total.vertices += stats.vertices;
+                    total.fragments += stats.fragments;
+                }
+                Err(e) => {
+                    result = Err(e);
+                    break;
+                }
+            }
+        }
+
+        result.map(|()| total)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::iter;
+
+    use renderer::{BlendMode, Renderer, SimpleRenderer};
+    use tri::Triangle;
+    use super::{DrawList, SortOrder};
+
+    #[test]
+    fn translucent() {
+        let near = Triangle::with_points([(0.0, 0.0), (4.0, 0.0), (0.0, 4.0)]);
+        let far = Triangle::with_points([(0.0, 0.0), (4.0, 0.0), (4.0, 4.0)]);
+        let (red, blue) = ((255, 0, 0, 128), (0, 0, 255, 128));
+
+        let render = |list: &mut DrawList<_, _>| {
+            let mut renderer = SimpleRenderer::single_buffered(4, 4);
+            renderer.set_blend(BlendMode::Over);
+            renderer.set_attr(0, (0, 255, 0, 255));
+            let stats = list.flush(&mut renderer).unwrap();
+            assert_eq!((stats.shapes, stats.vertices), (2, 6));
+            assert!(list.is_empty());
+            assert_eq!(renderer.get_attr(0), Some((0, 255, 0, 255)));
+            renderer.buffer().to_vec()
+        };
+
+        let mut list = DrawList::new(SortOrder::BackToFront);
+        list.push_with_attr(near, 1.0, red);
+        list.push_with_attr(far, 2.0, blue);
+        assert_eq!(list.len(), 2);
+        let near_first = render(&mut list);
+
+        list.push_with_attr(far, 2.0, blue);
+        list.push_with_attr(near, 1.0, red);
+        let far_first = render(&mut list);
+        assert_eq!(near_first, far_first);
+
+        let mut expected = SimpleRenderer::single_buffered(4, 4);
+        expected.set_blend(BlendMode::Over);
+        expected.set_attr(0, blue);
+        assert!(expected.draw(iter::once(far)).is_ok());
+        expected.set_attr(0, red);
+        assert!(expected.draw(iter::once(near)).is_ok());
+        assert_eq!(near_first, expected.buffer());
+
+        let mut list = DrawList::new(SortOrder::FrontToBack);
+        list.push_with_attr(far, 2.0, blue);
+        list.push_with_attr(near, 1.0, red);
+        assert_ne!(render(&mut list), expected.buffer());
+    }
+
+    #[test]
+    fn without_attr() {
+        let a = Triangle::with_points([(0.0, 0.0), (4.0, 0.0), (0.0, 4.0)]);
+        let b = Triangle::with_points([(4.0, 4.0), (4.0, 0.0), (0.0, 4.0)]);
+
+        let mut list = DrawList::new(SortOrder::BackToFront);
+        list.push_with_attr(a, 2.0, 1_u8);
+        list.push(b, 1.0);
+        let mut renderer = SimpleRenderer::single_buffered(4, 4);
+        assert!(list.flush(&mut renderer).is_ok());
+
+        // b isn't drawn with the attribute of a
+        assert_eq!(renderer.get_attr(0), None);
+        assert_eq!(renderer.buffer()[0], 1);
+        assert_eq!(renderer.buffer()[15], 0);
+
+        list.push_with_attr(a, 2.0, 1);
+        list.push(b, 1.0);
+        renderer.set_attr(0, 2);
+        assert!(list.flush(&mut renderer).is_ok());
+        assert_eq!(renderer.get_attr(0), Some(2));
+        assert_eq!(renderer.buffer()[15], 2);
+    }
+}
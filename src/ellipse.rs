@@ -0,0 +1,175 @@
+use num_traits::{AsPrimitive, Float};
+
+use renderer::Drawable;
+use point::Point2;
+use rect::{self, Rectangle};
+
+/// Primitive drawable filled axis-aligned ellipse.
+#[derive(Clone, Copy, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct Ellipse<T> {
+    center: Point2<T>,
+    rx: T,
+    ry: T,
+}
+
+impl<T> Ellipse<T> {
+    #[inline(always)]
+    pub fn new(center: Point2<T>, rx: T, ry: T) -> Self {
+        Ellipse { center, rx, ry }
+    }
+}
+
+impl<T: Float> Ellipse<T> {
+    /// Get the smallest `Rectangle` containing the ellipse.
+    #[inline]
+    pub fn bounding_box(&self) -> Rectangle<T> {
+        let (cx, cy) = self.center;
+        let (rx, ry) = (self.rx.abs(), self.ry.abs());
+        Rectangle::new(cx - rx, cx + rx, cy - ry, cy + ry)
+    }
+}
+
+impl<T: Float + AsPrimitive<i64> + 'static> Drawable<T, Point2<T>> for Ellipse<T>
+where
+    i64: AsPrimitive<T>,
+{
+    #[inline(always)]
+    fn vertices(&self) -> usize {
+        1
+    }
+}
+
+impl<T: Float + AsPrimitive<i64> + 'static> IntoIterator for Ellipse<T>
+where
+    i64: AsPrimitive<T>,
+{
+    type Item = Point2<T>;
+    type IntoIter = IntoIter<T>;
+
+    /// Iterate over all the pixels inside the ellipse.
+    ///
+    /// If one of the radii is zero, the ellipse collapses into a line of
+    /// pixels through its center. If both are zero, only the pixel
+    /// containing the center is yielded. An ellipse with a negative radius
+    /// yields nothing.
+    #[inline]
+    fn into_iter(self) -> Self::IntoIter {
+        let (cx, cy) = self.center;
+        let (rx, ry) = (self.rx, self.ry);
+
+        // on a collapsed axis, snap the center to the pixel containing it,
+        // so that the line of pixels passes the distance test
+        let cx = if rx == T::zero() { cx.floor() } else { cx };
+        let cy = if ry == T::zero() { cy.floor() } else { cy };
+
+        let rect = if rx < T::zero() || ry < T::zero() {
+            Rectangle::new(T::zero(), T::zero(), T::zero(), T::zero())
+        } else {
+            let x0 = (cx - rx).floor();
+            let x1 = (cx + rx).floor() + T::one();
+            let y0 = (cy - ry).floor();
+            let y1 = (cy + ry).floor() + T::one();
+            Rectangle::new(x0, x1, y0, y1)
+        };
+
+        IntoIter {
+            rect: rect.into_iter(),
+            center: (cx, cy),
+            rx,
+            ry,
+        }
+    }
+}
+
+#[derive(Debug)]
+pub struct IntoIter<T> {
+    rect: rect::IntoIter<T>,
+    center: Point2<T>,
+    rx: T,
+    ry: T,
+}
+
+impl<T: Float + 'static> Iterator for IntoIter<T>
+where
+    i64: AsPrimitive<T>,
+{
+    type Item = Point2<T>;
+
+    #[inline]
+    fn next(&mut self) -> Option<Self::Item> {
+        let (cx, cy) = self.center;
+        let (rx, ry) = (self.rx, self.ry);
+
+        // squared distance along one axis, normalized by the radius
+        let norm = |d: T, r: T| {
+            if r != T::zero() {
+                (d / r) * (d / r)
+            } else if d == T::zero() {
+                T::zero()
+            } else {
+                T::infinity()
+            }
+        };
+
+        self.rect
+            .find(|&(x, y)| norm(x - cx, rx) + norm(y - cy, ry) <= T::one())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::BTreeSet;
+
+    use super::Ellipse;
+
+    fn pixels(ellipse: Ellipse<f64>) -> BTreeSet<(i64, i64)> {
+        ellipse
+            .into_iter()
+            .map(|(x, y)| (x as i64, y as i64))
+            .collect()
+    }
+
+    #[test]
+    fn ellipse() {
+        let expected = [
+            (5, 4),
+            (2, 5),
+            (3, 5),
+            (4, 5),
+            (5, 5),
+            (6, 5),
+            (7, 5),
+            (8, 5),
+            (5, 6),
+        ];
+
+        assert_eq!(
+            pixels(Ellipse::new((5.0, 5.0), 3.0, 1.0)),
+            expected.iter().cloned().collect()
+        );
+    }
+
+    #[test]
+    fn degenerate() {
+        let expected = [(5, 3), (5, 4), (5, 5), (5, 6), (5, 7)];
+        assert_eq!(
+            pixels(Ellipse::new((5.5, 5.0), 0.0, 2.0)),
+            expected.iter().cloned().collect()
+        );
+
+        let expected = [(4, 5), (5, 5), (6, 5)];
+        assert_eq!(
+            pixels(Ellipse::new((5.0, 5.0), 1.0, 0.0)),
+            expected.iter().cloned().collect()
+        );
+
+        let expected = [(5, 5)];
+        assert_eq!(
+            pixels(Ellipse::new((5.0, 5.0), 0.0, 0.0)),
+            expected.iter().cloned().collect()
+        );
+
+        assert!(pixels(Ellipse::new((5.0, 5.0), -1.0, 2.0)).is_empty());
+    }
+}
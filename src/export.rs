@@ -0,0 +1,99 @@
+//! Conversion of the front buffer of a `SimpleRenderer` to `image` buffers.
+
+use std::path::Path;
+
+use image::{ImageBuffer, ImageResult, RgbImage, RgbaImage};
+
+use num_traits::{AsPrimitive, Float, Signed};
+
+use renderer::{Renderer, SimpleRenderer};
+
+impl<T: Float + Signed + AsPrimitive<usize>> SimpleRenderer<(u8, u8, u8), T> {
+    /// Copy the front buffer into an `RgbImage`.
+    pub fn to_image(&self) -> RgbImage {
+        let width = Renderer::<T>::width(self) as u32;
+        let height = Renderer::<T>::height(self) as u32;
+        let mut raw = Vec::with_capacity(self.buffer().len() * 3);
+        for &(r, g, b) in self.buffer() {
+            raw.extend_from_slice(&[r, g, b]);
+        }
+
+        ImageBuffer::from_raw(width, height, raw).unwrap()
+    }
+
+    /// Save the front buffer, i.e. the last frame `swap`-ped in, to `path`.
+    /// The format is deduced from the extension, i.e. PNG for `.png`.
+    pub fn save<P: AsRef<Path>>(&self, path: P) -> ImageResult<()> {
+        Ok(self.to_image().save(path)?)
+    }
+}
+
+impl<T: Float + Signed + AsPrimitive<usize>> SimpleRenderer<(u8, u8, u8, u8), T> {
+    /// Copy the front buffer into an `RgbaImage`.
+    pub fn to_image(&self) -> RgbaImage {
+        let width = Renderer::<T>::width(self) as u32;
+        let height = Renderer::<T>::height(self) as u32;
+        let mut raw = Vec::with_capacity(self.buffer().len() * 4);
+        for &(r, g, b, a) in self.buffer() {
+            raw.extend_from_slice(&[r, g, b, a]);
+        }
+
+        ImageBuffer::from_raw(width, height, raw).unwrap()
+    }
+
+    /// Save the front buffer, i.e. the last frame `swap`-ped in, to `path`.
+    /// The format is deduced from the extension, i.e. PNG for `.png`.
+    pub fn save<P: AsRef<Path>>(&self, path: P) -> ImageResult<()> {
+        Ok(self.to_image().save(path)?)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{env, fs, iter};
+
+    use image;
+
+    use renderer::{Renderer, SimpleRenderer};
+    use rect::Rectangle;
+
+    #[test]
+    fn to_image() {
+        let mut renderer = SimpleRenderer::new(8, 4);
+        renderer.set_attr(0, (10, 20, 30));
+        assert!(renderer.draw(iter::once(Rectangle::new(2.0, 6.0, 1.0, 3.0))).is_ok());
+        renderer.swap();
+
+        let image = renderer.to_image();
+        assert_eq!(image.dimensions(), (8, 4));
+        assert_eq!(image.get_pixel(3, 2).data, [10, 20, 30]);
+        assert_eq!(image.get_pixel(6, 2).data, [0, 0, 0]);
+
+        let mut renderer = SimpleRenderer::new(8, 4);
+        renderer.set_attr(0, (10, 20, 30, 40));
+        assert!(renderer.draw(iter::once(Rectangle::new(2.0, 6.0, 1.0, 3.0))).is_ok());
+        renderer.swap();
+
+        let image = renderer.to_image();
+        assert_eq!(image.get_pixel(5, 1).data, [10, 20, 30, 40]);
+        assert_eq!(image.get_pixel(5, 0).data, [0, 0, 0, 0]);
+    }
+
+    #[test]
+    fn save() {
+        let mut renderer = SimpleRenderer::new(8, 4);
+        renderer.set_attr(0, (10, 20, 30));
+        assert!(renderer.draw(iter::once(Rectangle::new(2.0, 6.0, 1.0, 3.0))).is_ok());
+        renderer.swap();
+
+        let path = env::temp_dir().join("qr-export-save.png");
+        renderer.save(&path).unwrap();
+        let image = image::open(&path).unwrap().to_rgb();
+        fs::remove_file(&path).unwrap();
+
+        assert_eq!(image.dimensions(), (8, 4));
+        assert_eq!(image.get_pixel(2, 1).data, [10, 20, 30]);
+        assert_eq!(image.get_pixel(5, 2).data, [10, 20, 30]);
+        assert_eq!(image.get_pixel(1, 1).data, [0, 0, 0]);
+    }
+}
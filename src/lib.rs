@@ -1,5 +1,5 @@
 #![allow(unknown_lints)]
-#![allow(inline_always)]
+#![allow(clippy::inline_always)]
 #![cfg_attr(feature = "check-docs", deny(missing_docs))]
 #![cfg_attr(all(test, feature = "nightly"), feature(test))]
 
@@ -23,16 +23,29 @@
 //!     let mesh = iter::once(triangle);
 //!
 //!     renderer.set_attr(0, (255_u8, 255_u8, 255_u8));
-//!     if let Ok((s, v, f)) = renderer.draw(mesh) {
-//!         println!("drawn {} primitives, {} vertices and {} fragments", s, v, f);
+//!     if let Ok(stats) = renderer.draw(mesh) {
+//!         println!(
+//!             "drawn {} primitives, {} vertices and {} fragments",
+//!             stats.shapes, stats.vertices, stats.fragments
+//!         );
 //!     }
 //! }
 //! ```
 
+#[cfg(feature = "image")]
 extern crate image;
 extern crate line_drawing;
 extern crate num_traits;
 extern crate rand;
+#[cfg(feature = "rayon")]
+extern crate rayon;
+#[cfg(feature = "window")]
+extern crate minifb;
+#[cfg(feature = "serde")]
+#[macro_use]
+extern crate serde_derive;
+#[cfg(all(test, feature = "serde"))]
+extern crate serde_json;
 #[cfg(all(test, feature = "nightly"))]
 extern crate test;
 
@@ -40,24 +53,66 @@ pub mod renderer;
 pub mod shape;
 pub mod point;
 pub mod line;
+pub mod bezier;
 pub mod rect;
 pub mod tri;
-
-pub use renderer::{Coord, Drawable, Renderer, SimpleRenderer};
-pub use shape::Shape;
-pub use point::{Point, Point2};
+pub mod tri3;
+pub mod circle;
+pub mod capsule;
+pub mod arc;
+pub mod ellipse;
+pub mod polygon;
+pub mod polyline;
+pub mod mesh;
+pub mod tee;
+pub mod draw_list;
+pub mod post;
+pub mod transform;
+pub mod combinator;
+pub mod project;
+pub mod texture;
+#[cfg(feature = "image")]
+pub mod export;
+#[cfg(feature = "window")]
+pub mod window;
+
+pub use renderer::{
+    Blend, BlendMode, ColorF32, ColorU8, Coord, Drawable, Pixel, RenderError, Renderer,
+    SimpleRenderer, SimpleRendererBuilder, Stats, StencilFunc,
+};
+pub use shape::{Scene, Shape, ShapeCoord};
+pub use point::{FatPoint, Point, Point2, Point3, PointCloud};
 pub use line::Line;
-pub use rect::Rectangle;
-pub use tri::Triangle;
+pub use bezier::{CubicBezier, QuadBezier};
+pub use rect::{GradientMode, GradientRect, Rectangle};
+pub use tri::{EdgeMask, InterpTri, Lerp3, Triangle, Winding};
+pub use tri3::Triangle3;
+pub use circle::Circle;
+pub use capsule::Capsule;
+// `arc::Arc` isn't re-exported, it'd collide with `std::sync::Arc`
+pub use arc::Sector;
+pub use ellipse::Ellipse;
+pub use polygon::Polygon;
+pub use polyline::Polyline;
+pub use mesh::{DepthTriangle, IndexedMesh, ProjectedMesh, TriFan, TriStrip};
+pub use tee::TeeRenderer;
+pub use draw_list::{DrawList, SortOrder};
+pub use transform::{Affine2, SceneBuilder, Transform};
+pub use combinator::{Translate, Translated};
+pub use project::{clip_near, Projection};
+pub use texture::{MipChain, Texture, WrapMode};
 
 #[cfg(test)]
 mod tests {
+    #[cfg(feature = "nightly")]
     use rand;
+    #[cfg(feature = "nightly")]
     use image::{ImageBuffer, Pixel, Rgb};
 
     #[cfg(feature = "nightly")]
     use test::{black_box, Bencher};
 
+    #[cfg(feature = "nightly")]
     use super::Triangle;
 
     #[cfg(feature = "nightly")]
@@ -116,6 +171,14 @@ mod tests {
         image.save("bench1.png").expect("couldn't save image");
     }
 
+    #[cfg(feature = "nightly")]
+    #[bench]
+    fn iter_tri(b: &mut Bencher) {
+        let triangle = Triangle::with_points([(3.5, 7.25), (1000.0, 96.0), (250.5, 1010.75)]);
+
+        b.iter(|| black_box(triangle).into_iter().count());
+    }
+
     #[cfg(feature = "nightly")]
     #[bench]
     fn white_tri(b: &mut Bencher) {
@@ -1,9 +1,12 @@
 use std::fmt::{self, Debug};
 
-use line_drawing::{FloatNum, Midpoint, SignedNum};
+use line_drawing::{FloatNum, Midpoint};
+use num_traits::AsPrimitive;
 
 use renderer::{Coord, Drawable};
 use point::Point2;
+use rect::{self, Rectangle};
+use transform::{Affine2, Transform};
 
 /// Euclidean + barycentric coordinate on a line.
 pub type Coordinate<T> = (Point2<T>, [T; 2]);
@@ -20,67 +23,838 @@ impl<T: Copy> Coord<T> for Coordinate<T> {
     }
 }
 
-#[derive(Clone, Copy, Debug)]
+#[derive(Clone, Copy, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct Line<T> {
     start: Point2<T>,
     end: Point2<T>,
+    width: T,
+    /// The interpolation factors at `start` and `end`, which differ from
+    /// `[0, 1]` if the line was clipped.
+    range: [T; 2],
 }
 
-impl<T: FloatNum + SignedNum> Drawable<T, Coordinate<T>> for Line<T> {
+impl<T: FloatNum> Line<T> {
+    #[inline(always)]
+    pub fn new(start: Point2<T>, end: Point2<T>) -> Self {
+        Line::with_width(start, end, T::zero())
+    }
+
+    /// Create a thick line, which is drawn as a rectangle `width` wide
+    /// along the segment between `start` and `end`. A zero-length thick
+    /// line is drawn as a `width` by `width` square around `start`.
+    ///
+    /// A `width` of zero or less draws the 1px line like `new` does.
+    #[inline(always)]
+    pub fn with_width(start: Point2<T>, end: Point2<T>, width: T) -> Self {
+        Line {
+            start,
+            end,
+            width,
+            range: [T::zero(), T::one()],
+        }
+    }
+
+    /// Clip the line to the closed bounds of `rect`, a.k.a.
+    /// Cohen-Sutherland, i.e. so that lines reaching far off-screen aren't
+    /// walked pixel by pixel.
+    ///
+    /// The clipped line keeps the direction and width of `self`, and the
+    /// interpolation factor of its `Coordinate`-s is still relative to the
+    /// original `start` and `end`.
+    ///
+    /// # Returns
+    ///
+    /// - `Some(Line)` with the part of the line inside of `rect`.
+    /// - `None` if the line lies entirely outside of `rect`.
+    #[inline]
+    pub fn clip(&self, rect: &Rectangle<T>) -> Option<Line<T>> {
+        const LEFT: u8 = 1;
+        const RIGHT: u8 = 2;
+        const TOP: u8 = 4;
+        const BOTTOM: u8 = 8;
+
+        let (min, max) = rect.min_max();
+        let code = |(x, y): Point2<T>| {
+            let mut code = 0;
+            if x < min.0 {
+                code |= LEFT;
+            } else if x > max.0 {
+                code |= RIGHT;
+            }
+            if y < min.1 {
+                code |= TOP;
+            } else if y > max.1 {
+                code |= BOTTOM;
+            }
+            code
+        };
+
+        let (mut a, mut b) = (self.start, self.end);
+        loop {
+            let (code_a, code_b) = (code(a), code(b));
+            if code_a | code_b == 0 {
+                break;
+            }
+            if code_a & code_b != 0 {
+                return None;
+            }
+
+            // move an outside endpoint onto the edge it lies beyond
+            let outside = if code_a != 0 { code_a } else { code_b };
+            let (dx, dy) = (b.0 - a.0, b.1 - a.1);
+            let p = if outside & (LEFT | RIGHT) != 0 {
+                let x = if outside & LEFT != 0 { min.0 } else { max.0 };
+                (x, a.1 + dy * (x - a.0) / dx)
+            } else {
+                let y = if outside & TOP != 0 { min.1 } else { max.1 };
+                (a.0 + dx * (y - a.1) / dy, y)
+            };
+
+            if outside == code_a {
+                a = p;
+            } else {
+                b = p;
+            }
+        }
+
+        // the interpolation factors of the new endpoints on the old line
+        let (dx, dy) = (self.end.0 - self.start.0, self.end.1 - self.start.1);
+        let [r0, r1] = self.range;
+        let factor = |(x, y): Point2<T>| {
+            let t = if dx.abs() >= dy.abs() && dx != T::zero() {
+                (x - self.start.0) / dx
+            } else if dy != T::zero() {
+                (y - self.start.1) / dy
+            } else {
+                T::zero()
+            };
+            r0 + (r1 - r0) * t
+        };
+
+        Some(Line {
+            start: a,
+            end: b,
+            width: self.width,
+            range: [factor(a), factor(b)],
+        })
+    }
+
+    /// Get the smallest `Rectangle` containing the endpoints, grown by half
+    /// the width on every side for thick lines.
+    #[inline]
+    pub fn bounding_box(&self) -> Rectangle<T> {
+        let half = self.width.max(T::zero()) / T::from(2.0).unwrap();
+        let (x0, x1) = (self.start.0.min(self.end.0), self.start.0.max(self.end.0));
+        let (y0, y1) = (self.start.1.min(self.end.1), self.start.1.max(self.end.1));
+
+        Rectangle::new(x0 - half, x1 + half, y0 - half, y1 + half)
+    }
+
+    /// Get the euclidean distance between `start` and `end`.
+    #[inline]
+    pub fn length(&self) -> T {
+        let (dx, dy) = (self.end.0 - self.start.0, self.end.1 - self.start.1);
+        (dx * dx + dy * dy).sqrt()
+    }
+
+    /// Iterate over the 1px line from `start` to `end` with a digital
+    /// differential analyzer, a.k.a. DDA, as an alternative to the midpoint
+    /// algorithm of `into_iter`.
+    ///
+    /// The endpoints are rounded to whole pixels. Every step advances the
+    /// major axis by one pixel and the minor axis by the slope, rounding it
+    /// to the nearest pixel, so the pixels are spaced uniformly. The first
+    /// element of the barycentric coordinate is the fraction of the steps
+    /// taken, like the distance along the line of `into_iter`.
+    #[inline]
+    pub fn dda(start: Point2<T>, end: Point2<T>) -> DdaIter<T> {
+        let start = (start.0.round(), start.1.round());
+        let end = (end.0.round(), end.1.round());
+        let (dx, dy) = (end.0 - start.0, end.1 - start.1);
+        let steps = dx.abs().max(dy.abs());
+        // a zero length line is a single pixel, and must not divide by zero
+        let (inc, steps_recip) = if steps > T::zero() {
+            ((dx / steps, dy / steps), steps.recip())
+        } else {
+            ((T::zero(), T::zero()), T::zero())
+        };
+
+        DdaIter {
+            start,
+            inc,
+            steps_recip,
+            index: 0,
+            pixels: steps.to_usize().unwrap_or(0) + 1,
+        }
+    }
+
+    /// Iterate over the anti-aliased 1px line from `start` to `end` with
+    /// Xiaolin Wu's algorithm, additionally yielding the intensity of each
+    /// pixel in `[0, 1]`.
+    ///
+    /// For every pixel along the major axis, the two pixels straddling the
+    /// line on the minor axis are yielded, with intensities summing to 1.
+    /// The endpoints are rounded to whole pixels along the major axis, like
+    /// `into_iter` does. Multiply the intensity into the output color for
+    /// smooth lines.
+    #[inline]
+    pub fn wu(start: Point2<T>, end: Point2<T>) -> WuIter<T> {
+        let dx = end.0 - start.0;
+        let dy = end.1 - start.1;
+        let steep = dy.abs() > dx.abs();
+        let (major, minor) = if steep {
+            ((start.1, end.1), (start.0, end.0))
+        } else {
+            ((start.0, end.0), (start.1, end.1))
+        };
+        let delta = major.1 - major.0;
+        let slope = if delta != T::zero() {
+            (minor.1 - minor.0) / delta
+        } else {
+            T::zero()
+        };
+
+        WuIter {
+            start: (major.0, minor.0),
+            first: major.0.round(),
+            step: if delta < T::zero() { -T::one() } else { T::one() },
+            slope,
+            delta,
+            steep,
+            index: 0,
+            pixels: (major.1.round() - major.0.round()).abs().to_usize().unwrap_or(0) + 1,
+            pending: None,
+        }
+    }
+}
+
+impl<T: FloatNum + 'static> Line<T>
+where
+    i64: AsPrimitive<T>,
+{
+    /// Iterate over the 1px line from `start` to `end` in a repeating
+    /// pattern of dashes `on` pixels long and gaps `off` pixels long,
+    /// starting with a dash.
+    ///
+    /// An `off` of zero yields the solid line, an `on` of zero yields
+    /// nothing.
+    #[inline]
+    pub fn dashed(start: Point2<T>, end: Point2<T>, on: T, off: T) -> DashedIter<T> {
+        DashedIter {
+            inner: Line::new(start, end).into_iter(),
+            start,
+            on,
+            period: on + off,
+        }
+    }
+
+    /// Iterate over the line like `into_iter`, additionally yielding the
+    /// coverage of each pixel in `[0, 1]`.
+    ///
+    /// Pixels between the endpoints are fully covered. The coverage of the
+    /// first and last pixel depends on the sub-pixel position of the
+    /// endpoints along the major axis, so moving an endpoint changes the
+    /// coverage smoothly instead of snapping by whole pixels.
+    ///
+    /// The width of the line is ignored, i.e. this always iterates over the
+    /// 1px line.
+    #[inline]
+    pub fn into_iter_subpixel(self) -> SubpixelIter<T> {
+        let half = T::from(0.5).unwrap();
+        let dx = self.end.0 - self.start.0;
+        let dy = self.end.1 - self.start.1;
+        let (start, end, delta) = if dx.abs() >= dy.abs() {
+            (self.start.0, self.end.0, dx)
+        } else {
+            (self.start.1, self.end.1, dy)
+        };
+
+        let (start_cov, end_cov) = if delta >= T::zero() {
+            (start.round() + half - start, end - end.round() + half)
+        } else {
+            (start - start.round() + half, end.round() + half - end)
+        };
+        let pixels = (end.round() - start.round()).abs().to_usize().unwrap_or(0) + 1;
+        let (start_cov, end_cov) = if pixels == 1 {
+            let cov = start_cov + end_cov - T::one();
+            (cov, cov)
+        } else {
+            (start_cov, end_cov)
+        };
+
+        SubpixelIter {
+            inner: Line {
+                width: T::zero(),
+                ..self
+            }
+            .into_iter(),
+            start_cov,
+            end_cov,
+            index: 0,
+            pixels,
+        }
+    }
+}
+
+impl<T: FloatNum> Transform<T> for Line<T> {
+    /// Transform the endpoints of the line. The width is scaled by the
+    /// square root of the area scale factor of `m`.
+    #[inline]
+    fn transform(&self, m: &Affine2<T>) -> Self {
+        Line {
+            start: m.apply(self.start),
+            end: m.apply(self.end),
+            width: self.width * m.det().abs().sqrt(),
+            range: self.range,
+        }
+    }
+}
+
+impl<T: FloatNum + 'static> Drawable<T, Coordinate<T>> for Line<T>
+where
+    i64: AsPrimitive<T>,
+{
     #[inline(always)]
     fn vertices(&self) -> usize {
         2
     }
 }
 
-impl<T: FloatNum + SignedNum> IntoIterator for Line<T> {
+impl<T: FloatNum + 'static> IntoIterator for Line<T>
+where
+    i64: AsPrimitive<T>,
+{
     type Item = Coordinate<T>;
     type IntoIter = IntoIter<T>;
 
+    /// Iterate over the pixels of the line.
+    ///
+    /// The first element of the barycentric coordinate is the distance from
+    /// `start` along the segment, as a fraction of its length. For a clipped
+    /// line, it's the fraction of the line before clipping.
     #[inline]
     fn into_iter(self) -> Self::IntoIter {
         let start = self.start;
         let dx = self.end.0 - self.start.0;
         let dy = self.end.1 - self.start.1;
-        let len = (dx * dx + dy * dy).sqrt();
+        let len = self.length();
         let len_recip = len.recip();
-        let inner = Midpoint::new(self.start, self.end);
+
+        let inner = if self.width > T::zero() {
+            let half = self.width / T::from(2.0).unwrap();
+            // the unit direction, or any direction for a square cap
+            let dir = if len > T::zero() {
+                (dx * len_recip, dy * len_recip)
+            } else {
+                (T::one(), T::zero())
+            };
+
+            // the bounding box of the corners, offset along the normal
+            let (nx, ny) = (-dir.1 * half, dir.0 * half);
+            let (ex, ey) = if len > T::zero() {
+                (T::zero(), T::zero())
+            } else {
+                (dir.0 * half, dir.1 * half)
+            };
+            let corners = [
+                (start.0 - ex + nx, start.1 - ey + ny),
+                (start.0 - ex - nx, start.1 - ey - ny),
+                (self.end.0 + ex + nx, self.end.1 + ey + ny),
+                (self.end.0 + ex - nx, self.end.1 + ey - ny),
+            ];
+            let (x0, x1, y0, y1) = corners.iter().fold(
+                (T::infinity(), T::neg_infinity(), T::infinity(), T::neg_infinity()),
+                |(x0, x1, y0, y1), &(x, y)| (x0.min(x), x1.max(x), y0.min(y), y1.max(y)),
+            );
+            let floor = |v: T| v.floor().to_i64().unwrap_or(0);
+            let rect = Rectangle::new(floor(x0), floor(x1) + 1, floor(y0), floor(y1) + 1);
+
+            Inner::Thick {
+                rect: rect.into_iter(),
+                dir,
+                half,
+                len,
+            }
+        } else {
+            // the midpoint algorithm steps once per pixel along the major
+            // axis, between the rounded endpoints
+            let steps = |a: T, b: T| (b.round() - a.round()).abs().to_usize().unwrap_or(0);
+            let cap = steps(self.start.0, self.end.0).max(steps(self.start.1, self.end.1)) + 1;
+            Inner::Thin(Midpoint::new(self.start, self.end), cap)
+        };
 
         IntoIter {
             start,
             inner,
             len_recip,
+            range: self.range,
         }
     }
 }
 
-pub struct IntoIter<T: FloatNum + SignedNum> {
+pub struct IntoIter<T: FloatNum> {
     start: Point2<T>,
-    inner: Midpoint<T, T>,
+    inner: Inner<T>,
     len_recip: T,
+    /// The interpolation factors at the endpoints, see `Line::clip`.
+    range: [T; 2],
 }
 
-impl<T: FloatNum + SignedNum> Iterator for IntoIter<T> {
+/// The pixels of either a 1px or a thick line.
+enum Inner<T: FloatNum> {
+    /// The midpoint iterator and an upper bound of its remaining pixels.
+    Thin(Midpoint<T, i64>, usize),
+    Thick {
+        rect: rect::IntoIter<i64>,
+        dir: Point2<T>,
+        half: T,
+        len: T,
+    },
+}
+
+impl<T: FloatNum + 'static> Iterator for IntoIter<T>
+where
+    i64: AsPrimitive<T>,
+{
     type Item = Coordinate<T>;
 
     #[inline]
     fn next(&mut self) -> Option<Self::Item> {
-        self.inner.next().map(|(x, y)| {
-            let dx = x - self.start.0;
-            let dy = y - self.start.1;
-            let dist = (dx * dx + dy * dy).sqrt();
-            let f = dist * self.len_recip;
-            ((x, y), [f, T::one() - f])
-        })
+        let start = self.start;
+        let [r0, r1] = self.range;
+        let factor = |f: T| {
+            let f = r0 + (r1 - r0) * f;
+            [f, T::one() - f]
+        };
+        match self.inner {
+            Inner::Thin(ref mut inner, ref mut cap) => {
+                *cap = cap.saturating_sub(1);
+                let len_recip = self.len_recip;
+                inner.next().map(|(x, y)| {
+                    let (x, y): Point2<T> = (x.as_(), y.as_());
+                    let dx = x - start.0;
+                    let dy = y - start.1;
+                    let dist = (dx * dx + dy * dy).sqrt();
+                    ((x, y), factor(dist * len_recip))
+                })
+            }
+            Inner::Thick {
+                ref mut rect,
+                dir,
+                half,
+                len,
+            } => {
+                // inside if the distance along the segment lies in
+                // `[0, len]`, or in `[-half, half)` for a square cap, and
+                // the distance along the normal lies in `[-half, half)`
+                let len_recip = self.len_recip;
+                let inside_along = |along: T| {
+                    if len > T::zero() {
+                        along >= T::zero() && along <= len
+                    } else {
+                        along >= -half && along < half
+                    }
+                };
+
+                rect.map(|(x, y)| (x.as_(), y.as_()))
+                    .find(|&(x, y): &Point2<T>| {
+                        let (dx, dy) = (x - start.0, y - start.1);
+                        let along = dx * dir.0 + dy * dir.1;
+                        let across = dx * dir.1 - dy * dir.0;
+                        inside_along(along) && across >= -half && across < half
+                    })
+                    .map(|(x, y)| {
+                        let (dx, dy) = (x - start.0, y - start.1);
+                        let f = if len > T::zero() {
+                            (dx * dir.0 + dy * dir.1) * len_recip
+                        } else {
+                            T::zero()
+                        };
+                        ((x, y), factor(f))
+                    })
+            }
+        }
+    }
+
+    /// The upper bound is one more than the Chebyshev distance between the
+    /// rounded endpoints for a 1px line, or the area of the bounding box for
+    /// a thick one.
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        match self.inner {
+            Inner::Thin(_, cap) => (0, Some(cap)),
+            Inner::Thick { ref rect, .. } => (0, rect.size_hint().1),
+        }
     }
 }
 
-impl<T: FloatNum + SignedNum + Debug> Debug for IntoIter<T> {
+impl<T: FloatNum + Debug> Debug for IntoIter<T> {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
         f.debug_struct("IntoIter")
             .field("start", &self.start)
             .field("len_recip", &self.len_recip)
+            .field("range", &self.range)
             .field("inner", &"...")
             .finish()
     }
 }
+
+/// An iterator over the pixels of the dashes of a line.
+///
+/// See `Line::dashed`.
+#[derive(Debug)]
+pub struct DashedIter<T: FloatNum> {
+    inner: IntoIter<T>,
+    start: Point2<T>,
+    on: T,
+    period: T,
+}
+
+impl<T: FloatNum + 'static> Iterator for DashedIter<T>
+where
+    i64: AsPrimitive<T>,
+{
+    type Item = Coordinate<T>;
+
+    #[inline]
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.on <= T::zero() {
+            return None;
+        }
+
+        let (start, on, period) = (self.start, self.on, self.period);
+        self.inner.find(|&((x, y), _)| {
+            let (dx, dy) = (x - start.0, y - start.1);
+            (dx * dx + dy * dy).sqrt() % period < on
+        })
+    }
+}
+
+/// An iterator over the pixels of a line and their coverage.
+///
+/// See `Line::into_iter_subpixel`.
+pub struct SubpixelIter<T: FloatNum> {
+    inner: IntoIter<T>,
+    start_cov: T,
+    end_cov: T,
+    index: usize,
+    pixels: usize,
+}
+
+impl<T: FloatNum + 'static> Iterator for SubpixelIter<T>
+where
+    i64: AsPrimitive<T>,
+{
+    type Item = (Coordinate<T>, T);
+
+    #[inline]
+    fn next(&mut self) -> Option<Self::Item> {
+        self.inner.next().map(|c| {
+            let cov = if self.index == 0 {
+                self.start_cov
+            } else if self.index + 1 == self.pixels {
+                self.end_cov
+            } else {
+                T::one()
+            };
+            self.index += 1;
+            (c, cov.max(T::zero()).min(T::one()))
+        })
+    }
+}
+
+impl<T: FloatNum + Debug> Debug for SubpixelIter<T> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("SubpixelIter")
+            .field("inner", &self.inner)
+            .field("start_cov", &self.start_cov)
+            .field("end_cov", &self.end_cov)
+            .field("index", &self.index)
+            .field("pixels", &self.pixels)
+            .finish()
+    }
+}
+
+/// An iterator over the pixels of a line stepped with a DDA.
+///
+/// See `Line::dda`.
+#[derive(Debug)]
+pub struct DdaIter<T> {
+    /// The rounded start of the line.
+    start: Point2<T>,
+    /// The change along both axes per step.
+    inc: Point2<T>,
+    /// The reciprocal of the count of steps, or zero for a single pixel.
+    steps_recip: T,
+    index: usize,
+    pixels: usize,
+}
+
+impl<T: FloatNum> Iterator for DdaIter<T> {
+    type Item = Coordinate<T>;
+
+    #[inline]
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.index == self.pixels {
+            return None;
+        }
+
+        // computed from the start every step, so that horizontal and
+        // vertical lines stay exact and rounding errors don't accumulate
+        let i = T::from(self.index).unwrap();
+        self.index += 1;
+        let x = (self.start.0 + self.inc.0 * i).round();
+        let y = (self.start.1 + self.inc.1 * i).round();
+        let f = i * self.steps_recip;
+
+        Some(((x, y), [f, T::one() - f]))
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let len = self.pixels - self.index;
+        (len, Some(len))
+    }
+}
+
+/// An iterator over the pixels of an anti-aliased line and their
+/// intensity.
+///
+/// See `Line::wu`.
+#[derive(Debug)]
+pub struct WuIter<T> {
+    /// The start of the line along the major and the minor axis.
+    start: Point2<T>,
+    /// The major axis position of the first pixel.
+    first: T,
+    /// The direction along the major axis, either 1 or -1.
+    step: T,
+    /// The change along the minor axis per unit along the major axis.
+    slope: T,
+    /// The length of the line along the major axis, signed.
+    delta: T,
+    /// Whether y is the major axis.
+    steep: bool,
+    index: usize,
+    pixels: usize,
+    /// The second pixel of the current pair.
+    pending: Option<(Coordinate<T>, T)>,
+}
+
+impl<T: FloatNum> Iterator for WuIter<T> {
+    type Item = (Coordinate<T>, T);
+
+    #[inline]
+    fn next(&mut self) -> Option<Self::Item> {
+        if let Some(pending) = self.pending.take() {
+            return Some(pending);
+        }
+        if self.index == self.pixels {
+            return None;
+        }
+
+        let major = self.first + self.step * T::from(self.index).unwrap();
+        self.index += 1;
+
+        let minor = self.start.1 + (major - self.start.0) * self.slope;
+        let (lo, frac) = (minor.floor(), minor - minor.floor());
+        let f = if self.delta != T::zero() {
+            ((major - self.start.0) / self.delta).max(T::zero()).min(T::one())
+        } else {
+            T::zero()
+        };
+        let steep = self.steep;
+        let coord = |minor: T| {
+            let p = if steep { (minor, major) } else { (major, minor) };
+            (p, [f, T::one() - f])
+        };
+
+        self.pending = Some((coord(lo + T::one()), frac));
+        Some((coord(lo), T::one() - frac))
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let len = (self.pixels - self.index) * 2 + self.pending.iter().count();
+        (len, Some(len))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::BTreeSet;
+
+    use super::Line;
+
+    #[test]
+    fn size_hint() {
+        use rand::{Rng, SeedableRng, XorShiftRng};
+
+        let mut rng = XorShiftRng::from_seed([0x2545_f491, 0x4f6c_dd1d, 0x9e37_79b9, 0x7f4a_7c15]);
+        for i in 0..200 {
+            let width = if i % 2 == 0 { 0.0 } else { rng.gen::<f64>() * 4.0 };
+            let mut point = || (rng.gen::<f64>() * 64.0 - 8.0, rng.gen::<f64>() * 64.0 - 8.0);
+            let line = Line::with_width(point(), point(), width);
+            let count = line.into_iter().count();
+
+            let mut iter = line.into_iter();
+            assert!(iter.size_hint().1.unwrap() >= count);
+            iter.nth(count / 2);
+            assert!(iter.size_hint().1.unwrap() >= iter.count());
+        }
+    }
+
+    #[test]
+    fn length() {
+        assert_eq!(Line::new((1.0, 2.0), (4.0, 6.0)).length(), 5.0);
+        assert_eq!(Line::with_width((4.0, 6.0), (1.0, 2.0), 3.0).length(), 5.0);
+        assert_eq!(Line::new((1.0, 2.0), (1.0, 2.0)).length(), 0.0);
+    }
+
+    #[test]
+    fn dashed() {
+        let pixels = |on, off| {
+            Line::dashed((0.0, 3.0), (9.0, 3.0), on, off)
+                .map(|((x, _), _)| x as i64)
+                .collect::<Vec<_>>()
+        };
+
+        assert_eq!(pixels(2.0, 2.0), [0, 1, 4, 5, 8, 9]);
+        assert_eq!(pixels(3.0, 1.0), [0, 1, 2, 4, 5, 6, 8, 9]);
+        assert_eq!(pixels(2.0, 0.0), (0..10).collect::<Vec<_>>());
+        assert!(pixels(0.0, 2.0).is_empty());
+    }
+
+    #[test]
+    fn thick() {
+        let pixels = Line::with_width((1.0, 5.0), (8.0, 5.0), 3.0)
+            .into_iter()
+            .map(|((x, y), _)| (x as i64, y as i64))
+            .collect::<BTreeSet<_>>();
+        let expected = (4..7)
+            .flat_map(|y| (1..9).map(move |x| (x, y)))
+            .collect::<BTreeSet<_>>();
+        assert_eq!(pixels, expected);
+
+        for ((x, _), [f, g]) in Line::with_width((1.0, 5.0), (8.0, 5.0), 3.0_f64) {
+            assert!((f - (x - 1.0) / 7.0).abs() < 1e-9);
+            assert!((f + g - 1.0).abs() < 1e-9);
+        }
+
+        let cap = Line::with_width((5.0, 5.0), (5.0, 5.0), 2.0).into_iter().count();
+        assert_eq!(cap, 4);
+
+        let thin = Line::with_width((1.0, 5.0), (8.0, 5.0), 0.0).into_iter().count();
+        assert_eq!(thin, 8);
+    }
+
+    #[test]
+    fn clip() {
+        use rect::Rectangle;
+
+        let rect = Rectangle::new(0.0, 10.0, 0.0, 10.0);
+        let ends = |line: Line<f64>| (line.start, line.end, line.range);
+
+        let inside = Line::new((1.0, 1.0), (8.0, 5.0));
+        assert_eq!(inside.clip(&rect).map(ends), Some(ends(inside)));
+
+        assert!(Line::new((-5.0, -5.0), (-1.0, 20.0)).clip(&rect).is_none());
+        assert!(Line::new((12.0, -3.0), (20.0, 5.0)).clip(&rect).is_none());
+        assert!(Line::new((-4.0, 2.0), (2.0, -4.0)).clip(&rect).is_none());
+
+        let clipped = Line::new((-2.0, 1.0), (6.0, 5.0)).clip(&rect).unwrap();
+        assert_eq!(ends(clipped), ((0.0, 2.0), (6.0, 5.0), [0.25, 1.0]));
+        assert_eq!(clipped.into_iter().next(), Some(((0.0, 2.0), [0.25, 0.75])));
+
+        let reversed = Line::new((6.0, 5.0), (-2.0, 1.0)).clip(&rect).unwrap();
+        assert_eq!(ends(reversed), ((6.0, 5.0), (0.0, 2.0), [0.0, 0.75]));
+
+        let both = Line::new((-5.0, 20.0), (15.0, -20.0)).clip(&rect).unwrap();
+        assert_eq!(ends(both), ((0.0, 10.0), (5.0, 0.0), [0.25, 0.5]));
+    }
+
+    #[test]
+    fn dda() {
+        let pixels = |iter: &mut dyn Iterator<Item = ((f64, f64), [f64; 2])>| {
+            iter.map(|(p, _)| p).collect::<Vec<_>>()
+        };
+
+        let diagonal = Line::new((1.0, 1.0), (7.0, 7.0));
+        let dda = pixels(&mut Line::dda((1.0, 1.0), (7.0, 7.0)));
+        assert_eq!(dda, pixels(&mut diagonal.into_iter()));
+
+        for &(start, end) in &[((2.0, 1.0), (2.0, 6.0)), ((6.0, 3.0), (1.0, 3.0))] {
+            let dda = pixels(&mut Line::dda(start, end));
+            assert_eq!(dda, pixels(&mut Line::new(start, end).into_iter()));
+        }
+        assert_eq!(pixels(&mut Line::dda((2.4, 2.6), (2.0, 3.0))), [(2.0, 3.0)]);
+
+        // both agree between whole pixels, but DDA snaps fractional
+        // endpoints first, unlike the midpoint algorithm
+        let dda = pixels(&mut Line::dda((0.0, 0.0), (8.0, 3.0)));
+        assert_eq!(dda, pixels(&mut Line::new((0.0, 0.0), (8.0, 3.0)).into_iter()));
+        assert_eq!(dda, pixels(&mut Line::dda((0.0, 0.3), (8.0, 2.6))));
+        assert_ne!(dda, pixels(&mut Line::new((0.0, 0.3), (8.0, 2.6)).into_iter()));
+
+        let factors = Line::dda((0.0, 0.0), (4.0, 0.0))
+            .map(|(_, [f, _])| f)
+            .collect::<Vec<_>>();
+        assert_eq!(factors, [0.0, 0.25, 0.5, 0.75, 1.0]);
+    }
+
+    #[test]
+    fn wu() {
+        for &(start, end) in &[((1.0, 1.0), (9.0, 6.0)), ((6.0, 9.0), (1.0, 1.0_f64))] {
+            let pixels = Line::wu(start, end).collect::<Vec<_>>();
+            assert_eq!(pixels.len(), 2 * 9);
+
+            let (first, last) = (pixels[0], pixels[pixels.len() - 2]);
+            assert_eq!((first.0).0, start);
+            assert_eq!(first.1, 1.0);
+            assert_eq!((last.0).0, end);
+            assert_eq!(last.1, 1.0);
+
+            for pair in pixels.chunks(2) {
+                let (((x0, y0), _), i0) = pair[0];
+                let (((x1, y1), _), i1) = pair[1];
+                assert_eq!((x1 - x0).abs() + (y1 - y0).abs(), 1.0);
+                assert!((i0 + i1 - 1.0).abs() < 1e-9);
+            }
+        }
+
+        let mut iter = Line::wu((0.0, 0.0), (4.0, 2.0)).skip(2);
+        assert_eq!(iter.next(), Some((((1.0, 0.0), [0.25, 0.75]), 0.5)));
+        assert_eq!(iter.next(), Some((((1.0, 1.0), [0.25, 0.75]), 0.5)));
+    }
+
+    #[test]
+    fn subpixel_end() {
+        let coverage = |end| {
+            Line::new((1.0, 2.0), (end, 2.0))
+                .into_iter_subpixel()
+                .map(|(((x, _), _), cov)| (x as i64, cov))
+                .collect::<Vec<_>>()
+        };
+
+        let mut last_total = 3.9;
+        for i in 0..10 {
+            let end = 5.0 + i as f64 * 0.1;
+            let pixels = coverage(end);
+            let total = pixels.iter().map(|&(_, cov)| cov).sum::<f64>();
+
+            assert!((total - last_total - 0.1).abs() < 1e-9);
+            assert!((total - (end - 1.0)).abs() < 1e-9);
+            last_total = total;
+
+            let (x, cov) = *pixels.last().unwrap();
+            if end < 5.5 {
+                assert_eq!(x, 5);
+                assert!((cov - (end - 4.5)).abs() < 1e-9);
+            } else {
+                assert_eq!(x, 6);
+                assert!((cov - (end - 5.5)).abs() < 1e-9);
+            }
+        }
+    }
+}
@@ -0,0 +1,367 @@
+//! Adapters from common vertex layouts to iterators over `Triangle`-s.
+
+use num_traits::{AsPrimitive, Float};
+
+use renderer::{Coord, Drawable};
+use point::{Point2, Point3};
+use project::Projection;
+use tri::{self, Triangle};
+
+/// A triangle mesh stored as a vertex buffer and an index buffer, where each
+/// triple of indices forms a triangle.
+#[derive(Clone, Debug, PartialEq)]
+pub struct IndexedMesh<T> {
+    pub vertices: Vec<Point2<T>>,
+    pub indices: Vec<[usize; 3]>,
+}
+
+impl<T> IndexedMesh<T> {
+    #[inline(always)]
+    pub fn new(vertices: Vec<Point2<T>>, indices: Vec<[usize; 3]>) -> Self {
+        IndexedMesh { vertices, indices }
+    }
+
+    /// Get the count of index triples referring to a missing vertex, which
+    /// `triangles` skips.
+    #[inline]
+    pub fn invalid_count(&self) -> usize {
+        let len = self.vertices.len();
+        self.indices
+            .iter()
+            .filter(|triple| triple.iter().any(|&i| i >= len))
+            .count()
+    }
+}
+
+impl<T: Float + AsPrimitive<i64>> IndexedMesh<T> {
+    /// Iterate over the triangles of the mesh, i.e. for `Renderer::draw`.
+    ///
+    /// Index triples referring to a missing vertex are skipped, see
+    /// `invalid_count`.
+    #[inline]
+    pub fn triangles<'a>(&'a self) -> impl Iterator<Item = Triangle<T>> + 'a {
+        let vertices = &self.vertices;
+        self.indices.iter().filter_map(move |&[a, b, c]| {
+            Some(Triangle::with_points([
+                *vertices.get(a)?,
+                *vertices.get(b)?,
+                *vertices.get(c)?,
+            ]))
+        })
+    }
+}
+
+/// A triangle strip, where every vertex after the first two forms a
+/// triangle with the two vertices before it.
+#[derive(Clone, Debug, PartialEq)]
+pub struct TriStrip<T>(pub Vec<Point2<T>>);
+
+impl<T: Float + AsPrimitive<i64>> TriStrip<T> {
+    /// Iterate over the triangles of the strip, i.e. for `Renderer::draw`.
+    ///
+    /// The first two vertices of every odd triangle are swapped, so that all
+    /// triangles have the winding of the first one. Strips with less than 3
+    /// vertices have no triangles.
+    #[inline]
+    pub fn triangles<'a>(&'a self) -> impl Iterator<Item = Triangle<T>> + 'a {
+        self.0.windows(3).enumerate().map(|(i, v)| {
+            if i % 2 == 0 {
+                Triangle::with_points([v[0], v[1], v[2]])
+            } else {
+                Triangle::with_points([v[1], v[0], v[2]])
+            }
+        })
+    }
+}
+
+/// A triangle fan, where every vertex after the first two forms a triangle
+/// with the vertex before it and the first vertex, i.e. the center.
+#[derive(Clone, Debug, PartialEq)]
+pub struct TriFan<T>(pub Vec<Point2<T>>);
+
+impl<T: Float + AsPrimitive<i64>> TriFan<T> {
+    /// Iterate over the triangles of the fan, i.e. for `Renderer::draw`.
+    ///
+    /// Fans with less than 3 vertices have no triangles.
+    #[inline]
+    pub fn triangles<'a>(&'a self) -> impl Iterator<Item = Triangle<T>> + 'a {
+        let center = self.0.first().cloned();
+        self.0
+            .get(1..)
+            .unwrap_or(&[])
+            .windows(2)
+            .filter_map(move |v| Some(Triangle::with_points([center?, v[0], v[1]])))
+    }
+}
+
+/// Euclidean + barycentric coordinate and depth on a `DepthTriangle`.
+pub type DepthCoordinate<T> = (Point2<T>, [T; 3], T);
+
+impl<T: Copy> Coord<T> for DepthCoordinate<T> {
+    #[inline(always)]
+    fn point(&self) -> Point2<T> {
+        self.0
+    }
+
+    #[inline(always)]
+    fn barycentric(&self) -> Option<&[T]> {
+        Some(&self.1)
+    }
+
+    #[inline(always)]
+    fn depth(&self) -> Option<T> {
+        Some(self.2)
+    }
+}
+
+/// A screenspace triangle with a depth at every vertex, which is
+/// interpolated linearly across the triangle, i.e. for depth testing.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct DepthTriangle<T> {
+    triangle: Triangle<T>,
+    depth: [T; 3],
+}
+
+impl<T> DepthTriangle<T> {
+    #[inline(always)]
+    pub fn new(triangle: Triangle<T>, depth: [T; 3]) -> Self {
+        DepthTriangle { triangle, depth }
+    }
+
+    /// Get the screenspace triangle.
+    #[inline(always)]
+    pub fn triangle(&self) -> &Triangle<T> {
+        &self.triangle
+    }
+
+    /// Get the depth of every vertex.
+    #[inline(always)]
+    pub fn depth(&self) -> &[T; 3] {
+        &self.depth
+    }
+}
+
+impl<T: Float + AsPrimitive<i64> + 'static> Drawable<T, DepthCoordinate<T>> for DepthTriangle<T>
+where
+    i64: AsPrimitive<T>,
+{
+    #[inline(always)]
+    fn vertices(&self) -> usize {
+        3
+    }
+
+    #[inline]
+    fn into_iter_viewport(self, width: usize, height: usize) -> DepthIter<T> {
+        DepthIter {
+            inner: self.triangle.into_iter_viewport(width, height),
+            depth: self.depth,
+        }
+    }
+}
+
+impl<T: Float + AsPrimitive<i64> + 'static> IntoIterator for DepthTriangle<T>
+where
+    i64: AsPrimitive<T>,
+{
+    type Item = DepthCoordinate<T>;
+    type IntoIter = DepthIter<T>;
+
+    #[inline]
+    fn into_iter(self) -> Self::IntoIter {
+        DepthIter {
+            inner: self.triangle.into_iter(),
+            depth: self.depth,
+        }
+    }
+}
+
+#[derive(Debug)]
+pub struct DepthIter<T> {
+    inner: tri::IntoIter<T>,
+    depth: [T; 3],
+}
+
+impl<T: Float + 'static> Iterator for DepthIter<T>
+where
+    i64: AsPrimitive<T>,
+{
+    type Item = DepthCoordinate<T>;
+
+    #[inline]
+    fn next(&mut self) -> Option<Self::Item> {
+        let d = self.depth;
+        self.inner
+            .next()
+            .map(|(p, b)| (p, b, b[0] * d[0] + b[1] * d[1] + b[2] * d[2]))
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.inner.size_hint()
+    }
+}
+
+/// An adapter projecting a mesh of 3D triangles onto the screen, i.e. to
+/// `draw` it with a depth buffer.
+///
+/// Triangles entirely behind the near plane are skipped. Triangles crossing
+/// it aren't clipped though, so vertices behind the viewer of a perspective
+/// projection end up mirrored. Clip them with `clip_near` first.
+#[derive(Clone, Debug)]
+pub struct ProjectedMesh<I, T> {
+    mesh: I,
+    projection: Projection<T>,
+    width: T,
+    height: T,
+}
+
+impl<I, T> ProjectedMesh<I, T> {
+    /// Project the triangles of `mesh` with `projection` onto a screen of
+    /// the given size, see `Projection::project_to`.
+    #[inline(always)]
+    pub fn new(mesh: I, projection: Projection<T>, width: T, height: T) -> Self {
+        ProjectedMesh {
+            mesh,
+            projection,
+            width,
+            height,
+        }
+    }
+}
+
+impl<I, T> Iterator for ProjectedMesh<I, T>
+where
+    I: Iterator<Item = [Point3<T>; 3]>,
+    T: Float + AsPrimitive<i64>,
+{
+    type Item = DepthTriangle<T>;
+
+    #[inline]
+    fn next(&mut self) -> Option<Self::Item> {
+        let projection = self.projection;
+        let v = self.mesh.find(|v| !v.iter().all(|&p| projection.behind_near(p)))?;
+        let (p0, d0) = projection.project_to(v[0], self.width, self.height);
+        let (p1, d1) = projection.project_to(v[1], self.width, self.height);
+        let (p2, d2) = projection.project_to(v[2], self.width, self.height);
+
+        Some(DepthTriangle::new(
+            Triangle::with_points([p0, p1, p2]),
+            [d0, d1, d2],
+        ))
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (0, self.mesh.size_hint().1)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use renderer::{Renderer, SimpleRenderer};
+    use tri::Triangle;
+    use super::{IndexedMesh, ProjectedMesh, TriFan, TriStrip};
+
+    #[test]
+    fn indexed_quad() {
+        let quad = [(1.0, 1.0), (7.0, 1.0), (7.0, 6.0), (1.0, 6.0)];
+        let mesh = IndexedMesh::new(quad.to_vec(), vec![[0, 1, 2], [0, 2, 3], [0, 2, 4]]);
+        assert_eq!(mesh.invalid_count(), 1);
+
+        let mut indexed = SimpleRenderer::<u8>::new(8, 8);
+        indexed.set_attr(0, 1);
+        let stats = indexed.draw(mesh.triangles()).unwrap();
+        assert_eq!(stats.shapes, 2);
+        indexed.swap();
+
+        let mut explicit = SimpleRenderer::<u8>::new(8, 8);
+        explicit.set_attr(0, 1);
+        let triangles = vec![
+            Triangle::with_points([quad[0], quad[1], quad[2]]),
+            Triangle::with_points([quad[0], quad[2], quad[3]]),
+        ];
+        assert!(explicit.draw(triangles.into_iter()).is_ok());
+        explicit.swap();
+
+        assert_eq!(indexed.buffer(), explicit.buffer());
+        assert_eq!(indexed.buffer().iter().filter(|&&px| px == 1).count(), 6 * 5);
+    }
+
+    #[test]
+    fn strip() {
+        let v = [(0.0, 0.0), (0.0, 4.0), (4.0, 0.0), (4.0, 4.0_f64)];
+        let triangles = TriStrip(v.to_vec()).triangles().collect::<Vec<_>>();
+
+        assert_eq!(triangles.len(), 2);
+        assert_eq!(triangles[0].points(), &[v[0], v[1], v[2]]);
+        assert_eq!(triangles[1].points(), &[v[2], v[1], v[3]]);
+        assert_eq!(triangles[0].det().signum(), triangles[1].det().signum());
+
+        assert_eq!(TriStrip(v[..2].to_vec()).triangles().count(), 0);
+    }
+
+    #[test]
+    fn fan() {
+        let v = [(2.0, 2.0), (4.0, 2.0), (4.0, 4.0), (2.0, 4.0), (0.0, 4.0)];
+        let triangles = TriFan(v.to_vec()).triangles().collect::<Vec<_>>();
+
+        assert_eq!(triangles.len(), 3);
+        assert_eq!(triangles[0].points(), &[v[0], v[1], v[2]]);
+        assert_eq!(triangles[1].points(), &[v[0], v[2], v[3]]);
+        assert_eq!(triangles[2].points(), &[v[0], v[3], v[4]]);
+
+        assert_eq!(TriFan(v[..2].to_vec()).triangles().count(), 0);
+        assert_eq!(TriFan(Vec::<(f64, f64)>::new()).triangles().count(), 0);
+    }
+
+    #[test]
+    fn projected() {
+        use project::Projection;
+
+        let ortho = Projection::ortho(-2.0, 2.0, -1.0, 1.0, 1.0, 3.0);
+        let mesh = vec![
+            [(0.0, 0.0, 1.0), (2.0, -1.0, 3.0), (-2.0, 1.0, 2.0)],
+            // entirely behind the near plane
+            [(0.0, 0.0, 0.5), (2.0, -1.0, 0.0), (-2.0, 1.0, -1.0)],
+            // partially behind it
+            [(0.0, 0.0, 0.5), (2.0, -1.0, 1.5), (-2.0, 1.0, 0.0)],
+        ];
+        let projected = ProjectedMesh::new(mesh.into_iter(), ortho, 128.0, 64.0)
+            .collect::<Vec<_>>();
+
+        assert_eq!(projected.len(), 2);
+        assert_eq!(
+            projected[0].triangle().points(),
+            &[(64.0, 32.0), (128.0, 64.0), (0.0, 0.0)]
+        );
+        assert_eq!(projected[0].depth(), &[0.0, 1.0, 0.5]);
+    }
+
+    #[test]
+    fn projected_depth() {
+        use project::Projection;
+
+        // a far quad drawn after a near one, which partly hides it
+        let ortho = Projection::ortho(0.0, 8.0, -8.0, 0.0, 0.0, 10.0);
+        let quad = |x0: f64, x1: f64, z: f64| {
+            vec![
+                [(x0, 0.0, z), (x1, 0.0, z), (x1, -8.0, z)],
+                [(x0, 0.0, z), (x1, -8.0, z), (x0, -8.0, z)],
+            ]
+        };
+        let mut mesh = quad(0.0, 4.0, 1.0);
+        mesh.extend(quad(2.0, 8.0, 5.0));
+
+        let mut renderer = SimpleRenderer::<u8>::with_depth(8, 8);
+        renderer.set_attr(0, 1);
+        let near = mesh.drain(..2).collect::<Vec<_>>();
+        assert!(renderer.draw(ProjectedMesh::new(near.into_iter(), ortho, 8.0, 8.0)).is_ok());
+        renderer.set_attr(0, 2);
+        assert!(renderer.draw(ProjectedMesh::new(mesh.into_iter(), ortho, 8.0, 8.0)).is_ok());
+        renderer.swap();
+
+        for (i, &px) in renderer.buffer().iter().enumerate() {
+            assert_eq!(px, if i % 8 < 4 { 1 } else { 2 }, "at {}", i);
+        }
+    }
+}
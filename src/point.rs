@@ -1,11 +1,20 @@
-use std::iter::{self, Once};
+use std::iter::{self, Cloned, Once};
+use std::slice;
 use std::ops::{Deref, DerefMut};
 
+use num_traits::{AsPrimitive, Float};
+
 use renderer::{Coord, Drawable};
+use rect::{self, Rectangle};
+use circle::{self, Circle};
+use transform::{Affine2, Transform};
 
 /// 2D euclidean point.
 pub type Point2<T> = (T, T);
 
+/// 3D euclidean point.
+pub type Point3<T> = (T, T, T);
+
 impl<T: Copy> Coord<T> for Point2<T> {
     #[inline(always)]
     fn point(&self) -> Point2<T> {
@@ -14,7 +23,8 @@ impl<T: Copy> Coord<T> for Point2<T> {
 }
 
 /// Primitive drawable point.
-#[derive(Clone, Copy, Debug)]
+#[derive(Clone, Copy, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct Point<T>(pub Point2<T>);
 
 impl<T: Copy> Drawable<T, Point2<T>> for Point<T> {
@@ -24,6 +34,15 @@ impl<T: Copy> Drawable<T, Point2<T>> for Point<T> {
     }
 }
 
+impl<T: Copy> Point<T> {
+    /// Get the zero-size `Rectangle` at the point.
+    #[inline(always)]
+    pub fn bounding_box(&self) -> Rectangle<T> {
+        let (x, y) = self.0;
+        Rectangle::new(x, x, y, y)
+    }
+}
+
 impl<T> Deref for Point<T> {
     type Target = Point2<T>;
 
@@ -40,6 +59,13 @@ impl<T> DerefMut for Point<T> {
     }
 }
 
+impl<T: Float> Transform<T> for Point<T> {
+    #[inline(always)]
+    fn transform(&self, m: &Affine2<T>) -> Self {
+        Point(m.apply(self.0))
+    }
+}
+
 /// Yo dawg, I heard you liked points, so I made the point containing a point
 /// into an iterator that returns that point exactly once.
 pub type IntoIter<T> = Once<Point2<T>>;
@@ -53,3 +79,197 @@ impl<T> IntoIterator for Point<T> {
         iter::once(self.0)
     }
 }
+
+impl<T> Point<T> {
+    /// Create a point drawn as a square of pixels reaching `radius` pixels
+    /// from `center` in every direction, i.e. a marker for plotting.
+    #[inline(always)]
+    pub fn fat(center: Point2<T>, radius: T) -> FatPoint<T> {
+        FatPoint {
+            center,
+            radius,
+            round: false,
+        }
+    }
+
+    /// Create a point drawn as a disc of pixels like `Circle`.
+    #[inline(always)]
+    pub fn fat_round(center: Point2<T>, radius: T) -> FatPoint<T> {
+        FatPoint {
+            center,
+            radius,
+            round: true,
+        }
+    }
+}
+
+/// Primitive drawable point, which covers more than a single pixel.
+///
+/// See `Point::fat` and `Point::fat_round`.
+#[derive(Clone, Copy, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct FatPoint<T> {
+    center: Point2<T>,
+    radius: T,
+    round: bool,
+}
+
+impl<T: Float> FatPoint<T> {
+    /// Get the smallest `Rectangle` containing the point.
+    #[inline]
+    pub fn bounding_box(&self) -> Rectangle<T> {
+        let (x, y) = self.center;
+        let r = self.radius.abs();
+        Rectangle::new(x - r, x + r, y - r, y + r)
+    }
+}
+
+impl<T: Float + AsPrimitive<i64> + 'static> Drawable<T, Point2<T>> for FatPoint<T>
+where
+    i64: AsPrimitive<T>,
+{
+    #[inline(always)]
+    fn vertices(&self) -> usize {
+        1
+    }
+}
+
+impl<T: Float + AsPrimitive<i64> + 'static> IntoIterator for FatPoint<T>
+where
+    i64: AsPrimitive<T>,
+{
+    type Item = Point2<T>;
+    type IntoIter = FatIter<T>;
+
+    /// Iterate over all the pixels of the square or disc.
+    ///
+    /// A point with a radius of zero yields only the pixel containing its
+    /// center, a point with a negative radius yields nothing.
+    #[inline]
+    fn into_iter(self) -> Self::IntoIter {
+        if self.round {
+            return FatIter {
+                inner: FatInner::Round(Circle::new(self.center, self.radius).into_iter()),
+            };
+        }
+
+        let (x, y) = self.center;
+        let r = self.radius;
+        let rect = if r < T::zero() {
+            Rectangle::new(T::zero(), T::zero(), T::zero(), T::zero())
+        } else {
+            let x0 = (x - r).floor();
+            let x1 = (x + r).floor() + T::one();
+            let y0 = (y - r).floor();
+            let y1 = (y + r).floor() + T::one();
+            Rectangle::new(x0, x1, y0, y1)
+        };
+
+        FatIter {
+            inner: FatInner::Square(rect.into_iter()),
+        }
+    }
+}
+
+#[derive(Debug)]
+pub struct FatIter<T> {
+    inner: FatInner<T>,
+}
+
+/// The pixels of either a square or a round point.
+#[derive(Debug)]
+enum FatInner<T> {
+    Square(rect::IntoIter<T>),
+    Round(circle::IntoIter<T>),
+}
+
+impl<T: Float + 'static> Iterator for FatIter<T>
+where
+    i64: AsPrimitive<T>,
+{
+    type Item = Point2<T>;
+
+    #[inline]
+    fn next(&mut self) -> Option<Self::Item> {
+        match self.inner {
+            FatInner::Square(ref mut square) => square.next(),
+            FatInner::Round(ref mut round) => round.next(),
+        }
+    }
+}
+
+/// Primitive drawable cloud of points, borrowed from a slice.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct PointCloud<'a, T: 'a>(pub &'a [Point2<T>]);
+
+impl<'a, T: Copy> Drawable<T, Point2<T>> for PointCloud<'a, T> {
+    #[inline(always)]
+    fn vertices(&self) -> usize {
+        self.0.len()
+    }
+}
+
+/// An iterator over the points of a `PointCloud`, each returned exactly once.
+pub type CloudIter<'a, T> = Cloned<slice::Iter<'a, Point2<T>>>;
+
+impl<'a, T: Copy> IntoIterator for PointCloud<'a, T> {
+    type Item = Point2<T>;
+    type IntoIter = CloudIter<'a, T>;
+
+    #[inline(always)]
+    fn into_iter(self) -> Self::IntoIter {
+        self.0.iter().cloned()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::iter;
+
+    use renderer::{Drawable, Renderer, SimpleRenderer};
+    use super::{Point, PointCloud};
+
+    #[test]
+    fn cloud() {
+        let points = [(1.0, 1.0), (3.0, 2.0), (2.0, 3.0)];
+        let cloud = PointCloud(&points);
+        let mut renderer = SimpleRenderer::<u8>::new(4, 4);
+
+        renderer.set_attr(0, 1);
+        let (shapes, vertices, fragments) = renderer.draw(iter::once(cloud)).unwrap().into();
+        assert_eq!((shapes, vertices, fragments), (1, 3, 3));
+
+        renderer.swap();
+        let lit = renderer
+            .buffer()
+            .iter()
+            .enumerate()
+            .filter(|&(_, &px)| px == 1)
+            .map(|(i, _)| (i % 4, i / 4))
+            .collect::<Vec<_>>();
+        assert_eq!(lit, [(1, 1), (3, 2), (2, 3)]);
+    }
+
+    #[test]
+    fn fat() {
+        let fat = Point::fat((5.0, 5.0), 1.0);
+        assert_eq!(fat.vertices(), 1);
+        let mut pixels = fat.into_iter().collect::<Vec<_>>();
+        pixels.sort_by(|a, b| a.partial_cmp(b).unwrap());
+        let mut expected = Vec::new();
+        for x in 4..7 {
+            for y in 4..7 {
+                expected.push((f64::from(x), f64::from(y)));
+            }
+        }
+        assert_eq!(pixels, expected);
+
+        assert_eq!(Point::fat((5.5, 5.5), 0.0).into_iter().collect::<Vec<_>>(), [(5.0, 5.0)]);
+        assert_eq!(Point::fat((5.0, 5.0), -1.0).into_iter().count(), 0);
+
+        // the corners of the square are cut off
+        let round = Point::fat_round((5.0, 5.0), 1.0).into_iter().collect::<Vec<_>>();
+        assert_eq!(round.len(), 5);
+        assert!(!round.contains(&(4.0, 4.0)));
+    }
+}
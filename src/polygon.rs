@@ -0,0 +1,507 @@
+use std::cmp::Ordering;
+use std::f64::consts::PI;
+use std::ops::Range;
+use std::vec;
+
+use num_traits::{AsPrimitive, Float};
+
+use renderer::Drawable;
+use point::Point2;
+use rect::Rectangle;
+use tri::Triangle;
+
+/// Simple polygon, given by its outline in either winding order.
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct Polygon<T> {
+    points: Vec<Point2<T>>,
+}
+
+impl<T> Polygon<T> {
+    #[inline(always)]
+    pub fn new(points: Vec<Point2<T>>) -> Self {
+        Polygon { points }
+    }
+
+    /// Get the outline of this polygon.
+    #[inline(always)]
+    pub fn points(&self) -> &[Point2<T>] {
+        &self.points
+    }
+}
+
+impl<T: Float> Polygon<T> {
+    /// Create a regular polygon with `sides` vertices on the circle around
+    /// `center` with `radius`, starting at the positive x axis.
+    ///
+    /// The vertices are ordered counter-clockwise in a y-up coordinate
+    /// system, i.e. the polygon has a positive `det`. Less than 3 `sides`
+    /// give an empty polygon.
+    #[inline]
+    pub fn regular(center: Point2<T>, radius: T, sides: usize) -> Self {
+        if sides < 3 {
+            return Polygon::new(Vec::new());
+        }
+
+        Polygon::new(around(center, sides, |_| radius))
+    }
+
+    /// Create a star with `points` tips on the circle around `center` with
+    /// `outer_r`, and the notches between them on the circle with
+    /// `inner_r`. The first tip lies on the positive x axis.
+    ///
+    /// The vertices are ordered counter-clockwise like `regular`. Less than
+    /// 2 `points` give an empty polygon.
+    #[inline]
+    pub fn star(center: Point2<T>, outer_r: T, inner_r: T, points: usize) -> Self {
+        if points < 2 {
+            return Polygon::new(Vec::new());
+        }
+
+        let radius = |i| if i % 2 == 0 { outer_r } else { inner_r };
+        Polygon::new(around(center, 2 * points, radius))
+    }
+
+    /// Get the smallest `Rectangle` containing the outline. An empty
+    /// polygon has a zero-size box at the origin.
+    #[inline]
+    pub fn bounding_box(&self) -> Rectangle<T> {
+        if self.points.is_empty() {
+            return Rectangle::new(T::zero(), T::zero(), T::zero(), T::zero());
+        }
+
+        let (x0, x1, y0, y1) = self.points.iter().fold(
+            (T::infinity(), T::neg_infinity(), T::infinity(), T::neg_infinity()),
+            |(x0, x1, y0, y1), &(x, y)| (x0.min(x), x1.max(x), y0.min(y), y1.max(y)),
+        );
+        Rectangle::new(x0, x1, y0, y1)
+    }
+}
+
+/// Place `n` points evenly around `center`, counter-clockwise from the
+/// positive x axis, with the i'th point `radius(i)` away from `center`.
+fn around<T: Float, F: Fn(usize) -> T>(
+    center: Point2<T>,
+    n: usize,
+    radius: F,
+) -> Vec<Point2<T>> {
+    let step = T::from(2.0 * PI).unwrap() / T::from(n).unwrap();
+    (0..n)
+        .map(|i| {
+            let (sin, cos) = (step * T::from(i).unwrap()).sin_cos();
+            let r = radius(i);
+            (center.0 + r * cos, center.1 + r * sin)
+        })
+        .collect()
+}
+
+impl<T: Float + AsPrimitive<i64>> Polygon<T> {
+    /// Twice the signed area of the polygon. Positive for counter-clockwise
+    /// outlines in a y-up coordinate system.
+    #[inline]
+    pub fn det(&self) -> T {
+        let n = self.points.len();
+        (0..n).fold(T::zero(), |acc, i| {
+            let (x0, y0) = self.points[i];
+            let (x1, y1) = self.points[(i + 1) % n];
+            acc + (x0 * y1 - x1 * y0)
+        })
+    }
+
+    /// Split the polygon into triangles by ear clipping.
+    ///
+    /// Concave polygons are supported, as long as the outline doesn't
+    /// intersect itself. All triangles have the same winding as the outline.
+    ///
+    /// # Returns
+    ///
+    /// - An empty `Vec` if the polygon has less than 3 points or zero area.
+    /// - An incomplete triangulation if the outline is self-intersecting,
+    ///   as clipping stops once no more ears can be found.
+    pub fn triangulate(&self) -> Vec<Triangle<T>> {
+        let det = self.det();
+        if self.points.len() < 3 || det == T::zero() {
+            return Vec::new();
+        }
+
+        let sign = det.signum();
+        let mut indices = (0..self.points.len()).collect::<Vec<_>>();
+        let mut triangles = Vec::with_capacity(indices.len() - 2);
+
+        while indices.len() > 3 {
+            let n = indices.len();
+            let ear = (0..n).find(|&i| {
+                let a = self.points[indices[(i + n - 1) % n]];
+                let b = self.points[indices[i]];
+                let c = self.points[indices[(i + 1) % n]];
+
+                cross(a, b, c) * sign > T::zero()
+                    && indices
+                        .iter()
+                        .map(|&j| self.points[j])
+                        .filter(|&p| p != a && p != b && p != c)
+                        .all(|p| !in_triangle(p, a, b, c, sign))
+            });
+
+            match ear {
+                Some(i) => {
+                    let a = self.points[indices[(i + n - 1) % n]];
+                    let b = self.points[indices[i]];
+                    let c = self.points[indices[(i + 1) % n]];
+                    triangles.push(Triangle::with_points([a, b, c]));
+                    indices.remove(i);
+                }
+                None => return triangles,
+            }
+        }
+
+        let points = [
+            self.points[indices[0]],
+            self.points[indices[1]],
+            self.points[indices[2]],
+        ];
+        triangles.push(Triangle::with_points(points));
+        triangles
+    }
+
+    /// Whether the outline turns in the same direction at every vertex.
+    /// Collinear vertices are ignored.
+    ///
+    /// Self-intersecting outlines, which wind around more than once, e.g. a
+    /// pentagram, aren't detected. Less than 3 points or zero area are never
+    /// convex.
+    pub fn is_convex(&self) -> bool {
+        let n = self.points.len();
+        if n < 3 {
+            return false;
+        }
+
+        let mut sign = T::zero();
+        for i in 0..n {
+            let a = self.points[(i + n - 1) % n];
+            let b = self.points[i];
+            let c = self.points[(i + 1) % n];
+            let turn = cross(a, b, c);
+            if turn == T::zero() {
+                continue;
+            }
+
+            let turn = turn.signum();
+            if sign == T::zero() {
+                sign = turn;
+            } else if turn != sign {
+                return false;
+            }
+        }
+
+        sign != T::zero()
+    }
+
+    /// Split the polygon into a fan of triangles around its first vertex,
+    /// i.e. to draw it with interpolated attributes by `draw`-ing the
+    /// triangles.
+    ///
+    /// Only correct for convex polygons, see `is_convex`, but much cheaper
+    /// than `triangulate`. Less than 3 points yield no triangles.
+    pub fn fan_triangles<'a>(&'a self) -> impl Iterator<Item = Triangle<T>> + 'a {
+        let first = self.points.first().cloned();
+        self.points
+            .windows(2)
+            .skip(1)
+            .filter_map(move |w| first.map(|a| Triangle::with_points([a, w[0], w[1]])))
+    }
+}
+
+impl<T: Float + AsPrimitive<i64> + 'static> Drawable<T, Point2<T>> for Polygon<T>
+where
+    i64: AsPrimitive<T>,
+{
+    #[inline(always)]
+    fn vertices(&self) -> usize {
+        self.points.len()
+    }
+}
+
+impl<T: Float + AsPrimitive<i64> + 'static> IntoIterator for Polygon<T>
+where
+    i64: AsPrimitive<T>,
+{
+    type Item = Point2<T>;
+    type IntoIter = IntoIter<T>;
+
+    /// Iterate over all the pixels inside the polygon, scanline by
+    /// scanline, using the even-odd fill rule.
+    ///
+    /// A polygon with less than 3 points yields nothing.
+    #[inline]
+    fn into_iter(self) -> Self::IntoIter {
+        let rows = if self.points.len() < 3 {
+            0..0
+        } else {
+            let (y0, y1) = self
+                .points
+                .iter()
+                .fold((T::infinity(), T::neg_infinity()), |(y0, y1), &(_, y)| {
+                    (y0.min(y), y1.max(y))
+                });
+            y0.ceil().as_()..y1.ceil().as_()
+        };
+
+        IntoIter {
+            points: self.points,
+            rows,
+            y: 0,
+            spans: Vec::new().into_iter(),
+            x: 0..0,
+        }
+    }
+}
+
+#[derive(Debug)]
+pub struct IntoIter<T> {
+    points: Vec<Point2<T>>,
+    rows: Range<i64>,
+    y: i64,
+    spans: vec::IntoIter<Range<i64>>,
+    x: Range<i64>,
+}
+
+impl<T: Float + 'static> IntoIter<T>
+where
+    i64: AsPrimitive<T>,
+{
+    /// The spans of pixels inside the polygon on the scanline `y`.
+    fn spans(&self, y: i64) -> Vec<Range<i64>> {
+        let y: T = y.as_();
+        let n = self.points.len();
+
+        // an edge crosses the scanline if exactly one of its ends lies on or
+        // above it, so horizontal edges never cross, and vertices shared by
+        // two crossing edges are only counted once
+        let mut xs = (0..n)
+            .map(|i| (self.points[i], self.points[(i + 1) % n]))
+            .filter(|&((_, ay), (_, by))| (ay <= y) != (by <= y))
+            .map(|((ax, ay), (bx, by))| ax + (y - ay) * (bx - ax) / (by - ay))
+            .collect::<Vec<_>>();
+        xs.sort_by(|a, b| a.partial_cmp(b).unwrap_or(Ordering::Equal));
+
+        xs.chunks(2)
+            .filter(|pair| pair.len() == 2)
+            .map(|pair| {
+                let x0 = pair[0].ceil().to_i64().unwrap_or(0);
+                let x1 = pair[1].ceil().to_i64().unwrap_or(0);
+                x0..x1
+            })
+            .collect()
+    }
+}
+
+impl<T: Float + 'static> Iterator for IntoIter<T>
+where
+    i64: AsPrimitive<T>,
+{
+    type Item = Point2<T>;
+
+    #[inline]
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            if let Some(x) = self.x.next() {
+                return Some((x.as_(), self.y.as_()));
+            }
+
+            match self.spans.next() {
+                Some(span) => self.x = span,
+                None => {
+                    self.y = self.rows.next()?;
+                    self.spans = self.spans(self.y).into_iter();
+                }
+            }
+        }
+    }
+}
+
+/// Twice the signed area of the triangle `abc`.
+#[inline(always)]
+fn cross<T: Float>(a: Point2<T>, b: Point2<T>, c: Point2<T>) -> T {
+    (b.0 - a.0) * (c.1 - a.1) - (b.1 - a.1) * (c.0 - a.0)
+}
+
+/// Whether `p` lies inside or on the edge of the triangle `abc` winding in
+/// the direction of `sign`.
+#[inline(always)]
+fn in_triangle<T: Float>(p: Point2<T>, a: Point2<T>, b: Point2<T>, c: Point2<T>, sign: T) -> bool {
+    cross(a, b, p) * sign >= T::zero()
+        && cross(b, c, p) * sign >= T::zero()
+        && cross(c, a, p) * sign >= T::zero()
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::BTreeSet;
+    use std::f64::consts::PI;
+
+    use super::Polygon;
+    use rect::Rectangle;
+
+    #[test]
+    fn regular() {
+        let square = Polygon::regular((5.0, 5.0), 2.0_f64, 4);
+        assert_eq!(square.points().len(), 4);
+        assert!((square.det() / 2.0 - 8.0).abs() < 1e-9);
+        assert!((square.points()[0].0 - 7.0).abs() < 1e-9);
+        assert!((square.points()[1].1 - 7.0).abs() < 1e-9);
+
+        // approaches the area of the circle
+        let circle = Polygon::regular((0.0, 0.0), 1.0, 256);
+        assert!((circle.det() / 2.0 - PI).abs() < 1e-3);
+
+        assert!(Polygon::regular((0.0, 0.0), 1.0, 2).points().is_empty());
+    }
+
+    #[test]
+    fn star() {
+        let star = Polygon::star((0.0, 0.0), 2.0, 1.0, 5);
+        assert_eq!(star.points().len(), 10);
+        assert!(star.det() > 0.0);
+        // 10 triangles between the center and the outline
+        let tip = 0.5 * 2.0 * 1.0 * (PI / 5.0).sin();
+        assert!((star.det() / 2.0 - 10.0 * tip).abs() < 1e-9);
+        assert_eq!(star.triangulate().len(), 8);
+
+        assert!(Polygon::star((0.0, 0.0), 2.0, 1.0, 1).points().is_empty());
+    }
+
+    #[test]
+    fn triangulate_l() {
+        let polygon = Polygon::new(vec![
+            (0.0, 0.0),
+            (4.0, 0.0),
+            (4.0, 2.0),
+            (2.0, 2.0),
+            (2.0, 4.0),
+            (0.0, 4.0),
+        ]);
+        let triangles = polygon.triangulate();
+        assert_eq!(triangles.len(), 4);
+        assert!(triangles.iter().all(|tri| tri.det() > 0.0));
+
+        let filled = triangles
+            .into_iter()
+            .flat_map(|tri| tri.into_iter())
+            .map(|((x, y), _)| (x as i64, y as i64))
+            .collect::<BTreeSet<_>>();
+        let scanline = Rectangle::new(0, 4, 0, 2)
+            .into_iter()
+            .chain(Rectangle::new(0, 2, 2, 4))
+            .collect::<BTreeSet<_>>();
+
+        assert_eq!(filled, scanline);
+        assert_eq!(
+            Polygon::new(polygon.points().to_vec())
+                .into_iter()
+                .map(|(x, y)| (x as i64, y as i64))
+                .collect::<BTreeSet<_>>(),
+            scanline
+        );
+    }
+
+    #[test]
+    fn fan_triangles() {
+        let pentagon = Polygon::regular((5.0, 5.0), 4.0_f64, 5);
+        assert!(pentagon.is_convex());
+
+        let triangles = pentagon.fan_triangles().collect::<Vec<_>>();
+        assert_eq!(triangles.len(), 3);
+        assert!(triangles.iter().all(|tri| tri.points()[0] == pentagon.points()[0]));
+        let area = triangles.iter().map(|tri| tri.det()).sum::<f64>();
+        assert!((area - pentagon.det()).abs() < 1e-9);
+
+        let arrow = Polygon::new(vec![(0.0, 0.0), (8.0, 4.0), (0.0, 8.0), (3.0, 4.0)]);
+        assert!(!arrow.is_convex());
+        // clockwise, and with a collinear vertex
+        let square = Polygon::new(vec![(0.0, 0.0), (0.0, 2.0), (2.0, 2.0), (2.0, 1.0), (2.0, 0.0)]);
+        assert!(square.is_convex());
+        assert!(!Polygon::new(vec![(0.0, 0.0), (1.0, 1.0), (2.0, 2.0)]).is_convex());
+        assert_eq!(Polygon::new(vec![(0.0, 0.0), (1.0, 1.0)]).fan_triangles().count(), 0);
+    }
+
+    #[test]
+    fn triangulate_collinear() {
+        let pixels = |polygon: &Polygon<f64>| {
+            polygon
+                .triangulate()
+                .into_iter()
+                .flat_map(|tri| tri.into_iter())
+                .map(|((x, y), _)| (x as i64, y as i64))
+                .collect::<BTreeSet<_>>()
+        };
+
+        // a square with a vertex in the middle of every edge
+        let square = Polygon::new(vec![
+            (0.0, 0.0),
+            (2.0, 0.0),
+            (4.0, 0.0),
+            (4.0, 2.0),
+            (4.0, 4.0),
+            (2.0, 4.0),
+            (0.0, 4.0),
+            (0.0, 2.0),
+        ]);
+        assert_eq!(square.triangulate().len(), 6);
+        assert_eq!(pixels(&square), Rectangle::new(0, 4, 0, 4).into_iter().collect());
+
+        // a concave "U" with collinear vertices along the notch
+        let u = Polygon::new(vec![
+            (0.0, 0.0),
+            (6.0, 0.0),
+            (6.0, 6.0),
+            (4.0, 6.0),
+            (4.0, 4.0),
+            (4.0, 2.0),
+            (3.0, 2.0),
+            (2.0, 2.0),
+            (2.0, 4.0),
+            (2.0, 6.0),
+            (0.0, 6.0),
+        ]);
+        let scanline = u
+            .clone()
+            .into_iter()
+            .map(|(x, y)| (x as i64, y as i64))
+            .collect::<BTreeSet<_>>();
+        assert_eq!(u.triangulate().len(), 9);
+        assert_eq!(pixels(&u), scanline);
+
+        // degenerate outlines
+        assert!(Polygon::new(vec![(0.0, 0.0), (1.0, 1.0), (3.0, 3.0)]).triangulate().is_empty());
+        assert!(Polygon::new(vec![(0.0, 0.0), (1.0, 1.0)]).triangulate().is_empty());
+        assert!(Polygon::<f64>::new(Vec::new()).triangulate().is_empty());
+    }
+
+    #[test]
+    fn fill_arrow() {
+        let arrow = Polygon::new(vec![(0.0, 0.0), (8.0, 4.0), (0.0, 8.0), (3.0, 4.0)]);
+        let filled = arrow
+            .into_iter()
+            .map(|(x, y)| (x as i64, y as i64))
+            .collect::<BTreeSet<_>>();
+
+        for p in &[(2, 2), (3, 2), (3, 4), (6, 4), (7, 4), (2, 6)] {
+            assert!(filled.contains(p));
+        }
+        // the notch and the outside of the sloped edges
+        for p in &[(0, 4), (1, 4), (2, 4), (1, 2), (4, 2), (8, 4)] {
+            assert!(!filled.contains(p));
+        }
+
+        let line = Polygon::new(vec![(0.0, 0.0), (8.0, 8.0)]);
+        assert_eq!(line.into_iter().count(), 0);
+    }
+
+    #[test]
+    fn fill_nan() {
+        // the crossings of the edges at the NaN vertex are NaN themselves
+        let polygon = Polygon::new(vec![(0.0, 0.0), (f64::NAN, 4.0), (0.0, 8.0), (8.0, 4.0)]);
+        assert!(polygon.into_iter().count() <= 8 * 8);
+    }
+}
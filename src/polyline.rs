@@ -0,0 +1,177 @@
+use line_drawing::FloatNum;
+use num_traits::{AsPrimitive, Float};
+
+use renderer::Drawable;
+use point::Point2;
+use line::{self, Coordinate, Line};
+use rect::Rectangle;
+
+/// Primitive drawable open path of 1px lines through its points.
+///
+/// Unlike `Polygon`, the path isn't closed and isn't filled.
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct Polyline<T> {
+    points: Vec<Point2<T>>,
+}
+
+impl<T> Polyline<T> {
+    #[inline(always)]
+    pub fn new(points: Vec<Point2<T>>) -> Self {
+        Polyline { points }
+    }
+
+    /// Get the points along the path.
+    #[inline(always)]
+    pub fn points(&self) -> &[Point2<T>] {
+        &self.points
+    }
+}
+
+impl<T: Float> Polyline<T> {
+    /// Get the smallest `Rectangle` containing the path. An empty path has a
+    /// zero-size box at the origin.
+    #[inline]
+    pub fn bounding_box(&self) -> Rectangle<T> {
+        if self.points.is_empty() {
+            return Rectangle::new(T::zero(), T::zero(), T::zero(), T::zero());
+        }
+
+        let (x0, x1, y0, y1) = self.points.iter().fold(
+            (T::infinity(), T::neg_infinity(), T::infinity(), T::neg_infinity()),
+            |(x0, x1, y0, y1), &(x, y)| (x0.min(x), x1.max(x), y0.min(y), y1.max(y)),
+        );
+        Rectangle::new(x0, x1, y0, y1)
+    }
+}
+
+impl<T: FloatNum + 'static> Drawable<T, Coordinate<T>> for Polyline<T>
+where
+    i64: AsPrimitive<T>,
+{
+    #[inline(always)]
+    fn vertices(&self) -> usize {
+        self.points.len()
+    }
+}
+
+impl<T: FloatNum + 'static> IntoIterator for Polyline<T>
+where
+    i64: AsPrimitive<T>,
+{
+    type Item = Coordinate<T>;
+    type IntoIter = IntoIter<T>;
+
+    /// Iterate over the pixels of every segment in order.
+    ///
+    /// The interpolation factor of the `Coordinate`-s runs from 0 at the
+    /// first point to 1 at the last one, proportionally to the length along
+    /// the whole path. The pixel shared by two segments is only yielded
+    /// once. A path with less than 2 points yields nothing.
+    #[inline]
+    fn into_iter(self) -> Self::IntoIter {
+        let mut offsets = Vec::with_capacity(self.points.len());
+        let mut total = T::zero();
+        for (i, &(x, y)) in self.points.iter().enumerate() {
+            if i > 0 {
+                let (px, py) = self.points[i - 1];
+                total += ((x - px) * (x - px) + (y - py) * (y - py)).sqrt();
+            }
+            offsets.push(total);
+        }
+
+        IntoIter {
+            points: self.points,
+            offsets,
+            total,
+            segment: 0,
+            line: None,
+        }
+    }
+}
+
+#[derive(Debug)]
+pub struct IntoIter<T: FloatNum> {
+    points: Vec<Point2<T>>,
+    /// The length along the path at every point.
+    offsets: Vec<T>,
+    total: T,
+    /// The index of the next segment to walk.
+    segment: usize,
+    line: Option<line::IntoIter<T>>,
+}
+
+impl<T: FloatNum + 'static> Iterator for IntoIter<T>
+where
+    i64: AsPrimitive<T>,
+{
+    type Item = Coordinate<T>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            if let Some((p, [f, _])) = self.line.as_mut().and_then(|line| line.next()) {
+                // the segment of the current line
+                let i = self.segment - 1;
+                let (start, end) = (self.offsets[i], self.offsets[i + 1]);
+                let along = if end > start {
+                    start + (end - start) * f
+                } else {
+                    start
+                };
+                let t = if self.total > T::zero() {
+                    along / self.total
+                } else {
+                    T::zero()
+                };
+                return Some((p, [t, T::one() - t]));
+            }
+
+            let i = self.segment;
+            if i + 1 >= self.points.len() {
+                self.line = None;
+                return None;
+            }
+
+            let mut line = Line::new(self.points[i], self.points[i + 1]).into_iter();
+            if i > 0 {
+                // the first pixel is the last one of the previous segment
+                line.next();
+            }
+            self.line = Some(line);
+            self.segment += 1;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Polyline;
+
+    #[test]
+    fn polyline() {
+        let points = vec![(0.0, 0.0), (5.0, 0.0), (5.0, 5.0)];
+        let coords = Polyline::new(points.clone()).into_iter().collect::<Vec<_>>();
+        assert_eq!(coords.len(), 11);
+
+        for p in points {
+            assert!(coords.iter().any(|c| c.0 == p));
+        }
+        assert_eq!(coords[0].1, [0.0, 1.0]);
+        assert_eq!(coords[5], ((5.0, 0.0), [0.5, 0.5]));
+        assert_eq!(coords[10].1, [1.0, 0.0]);
+        for pair in coords.windows(2) {
+            assert!(pair[1].1[0] > pair[0].1[0]);
+        }
+    }
+
+    #[test]
+    fn degenerate() {
+        assert_eq!(Polyline::<f64>::new(Vec::new()).into_iter().count(), 0);
+        assert_eq!(Polyline::new(vec![(1.0, 1.0)]).into_iter().count(), 0);
+
+        let coords = Polyline::new(vec![(1.0, 1.0), (1.0, 1.0)])
+            .into_iter()
+            .collect::<Vec<_>>();
+        assert_eq!(coords, [((1.0, 1.0), [0.0, 1.0])]);
+    }
+}
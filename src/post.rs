@@ -0,0 +1,476 @@
+//! Post-processing effects operating on the front buffer of a
+//! `SimpleRenderer`.
+
+use std::f64;
+
+use num_traits::{AsPrimitive, Float, Signed};
+
+use renderer::{Renderer, SimpleRenderer};
+
+/// A squared distance standing in for infinity, small enough to not turn
+/// into `NaN` when subtracted from itself.
+const FAR: f64 = 1e20;
+
+impl<Px: Default + PartialEq + Clone, T> SimpleRenderer<Px, T>
+where
+    T: Float + Signed + AsPrimitive<usize>,
+{
+    /// Compute the euclidean distance of every pixel of the front buffer to
+    /// the nearest filled pixel, i.e. for glow or soft shadow effects.
+    /// A pixel is filled if it's not equal to `Px::default()`.
+    ///
+    /// # Returns
+    ///
+    /// A `Vec` of distances in pixels, in the same layout as `buffer`.
+    /// Filled pixels have a distance of zero. All distances are infinite if
+    /// there are no filled pixels.
+    pub fn distance_field(&self) -> Vec<f32> {
+        let width = Renderer::<T>::width(self);
+        let height = Renderer::<T>::height(self);
+        let empty = Px::default();
+
+        let mut field = self
+            .buffer()
+            .iter()
+            .map(|px| if *px == empty { FAR } else { 0.0 })
+            .collect::<Vec<_>>();
+
+        let mut column = vec![0.0; height];
+        let mut out = vec![0.0; width.max(height)];
+        for x in 0..width {
+            for y in 0..height {
+                column[y] = field[y * width + x];
+            }
+            distance_1d(&column, &mut out[..height]);
+            for y in 0..height {
+                field[y * width + x] = out[y];
+            }
+        }
+
+        for row in field.chunks_mut(width.max(1)) {
+            distance_1d(row, &mut out[..width]);
+            row.copy_from_slice(&out[..width]);
+        }
+
+        field
+            .into_iter()
+            .map(|d| {
+                if d >= FAR * 0.5 {
+                    f32::INFINITY
+                } else {
+                    d.sqrt() as f32
+                }
+            })
+            .collect()
+    }
+}
+
+impl<T: Float + Signed + AsPrimitive<usize>> SimpleRenderer<(u8, u8, u8), T> {
+    /// Make bright areas of the front buffer glow.
+    ///
+    /// Pixels with a luminance above `threshold` are extracted, blurred with
+    /// a gaussian kernel and added back onto the image, scaled by
+    /// `intensity`.
+    ///
+    /// # Parameters
+    ///
+    /// - `threshold` the luminance in `[0, 1]` above which pixels glow.
+    /// - `radius` the radius of the blur in pixels.
+    /// - `intensity` the factor the glow gets scaled by.
+    pub fn bloom(&mut self, threshold: f64, radius: usize, intensity: f64) {
+        let width = Renderer::<T>::width(self);
+        let height = Renderer::<T>::height(self);
+
+        let mut bright = self
+            .buffer()
+            .iter()
+            .map(|&(r, g, b)| {
+                let (r, g, b) = (f64::from(r), f64::from(g), f64::from(b));
+                if (0.2126 * r + 0.7152 * g + 0.0722 * b) / 255.0 > threshold {
+                    (r, g, b)
+                } else {
+                    (0.0, 0.0, 0.0)
+                }
+            })
+            .collect::<Vec<_>>();
+        gaussian_blur(&mut bright, width, height, radius);
+
+        let add = |c: u8, glow: f64| (f64::from(c) + glow * intensity).round().min(255.0) as u8;
+        for (px, (r, g, b)) in self.buffer_mut().iter_mut().zip(bright) {
+            *px = (add(px.0, r), add(px.1, g), add(px.2, b));
+        }
+    }
+
+    /// Darken the front buffer towards its corners.
+    ///
+    /// Every pixel is scaled by `1 - strength * d^2`, where `d` is its
+    /// distance from the center, normalized so that the corners are at a
+    /// distance of 1. A `strength` of 0 leaves the image unchanged, a
+    /// `strength` of 1 turns the corners black.
+    pub fn vignette(&mut self, strength: f64) {
+        let width = Renderer::<T>::width(self);
+        let height = Renderer::<T>::height(self);
+        let cx = (width as f64 - 1.0) / 2.0;
+        let cy = (height as f64 - 1.0) / 2.0;
+        let max_recip = (cx * cx + cy * cy).recip();
+
+        for (i, px) in self.buffer_mut().iter_mut().enumerate() {
+            let dx = (i % width) as f64 - cx;
+            let dy = (i / width) as f64 - cy;
+            let d = if max_recip.is_finite() {
+                (dx * dx + dy * dy) * max_recip
+            } else {
+                0.0
+            };
+            let f = (1.0 - strength * d).max(0.0);
+            let scale = |c: u8| (f64::from(c) * f).round().min(255.0) as u8;
+            *px = (scale(px.0), scale(px.1), scale(px.2));
+        }
+    }
+
+    /// Simulate lens dispersion by sampling the red and blue channels of the
+    /// front buffer at positions scaled radially from the center, by
+    /// `1 + amount` and `1 - amount` respectively. The green channel is left
+    /// untouched. Samples outside of the buffer are clamped to its edges.
+    ///
+    /// This produces colored fringes which grow towards the edges of the
+    /// image, while its center remains sharp.
+    pub fn chromatic_aberration(&mut self, amount: f64) {
+        let width = Renderer::<T>::width(self);
+        let height = Renderer::<T>::height(self);
+        let cx = (width as f64 - 1.0) / 2.0;
+        let cy = (height as f64 - 1.0) / 2.0;
+        let src = self.buffer().to_vec();
+
+        let sample = |x: usize, y: usize, scale: f64| {
+            let sx = (cx + (x as f64 - cx) * scale).round();
+            let sy = (cy + (y as f64 - cy) * scale).round();
+            let sx = sx.max(0.0).min(width as f64 - 1.0) as usize;
+            let sy = sy.max(0.0).min(height as f64 - 1.0) as usize;
+            src[sy * width + sx]
+        };
+
+        for (i, px) in self.buffer_mut().iter_mut().enumerate() {
+            let (x, y) = (i % width, i / width);
+            px.0 = sample(x, y, 1.0 + amount).0;
+            px.2 = sample(x, y, 1.0 - amount).2;
+        }
+    }
+
+    /// Quantize every channel of the front buffer to `levels` evenly spaced
+    /// values, including 0 and 255. A `levels` of less than 2 is treated as
+    /// 2, producing one bit per channel.
+    pub fn posterize(&mut self, levels: u8) {
+        let steps = f64::from(levels.max(2) - 1);
+        let quantize =
+            |c: u8| ((f64::from(c) * steps / 255.0).round() * 255.0 / steps).round() as u8;
+
+        for px in self.buffer_mut() {
+            *px = (quantize(px.0), quantize(px.1), quantize(px.2));
+        }
+    }
+
+    /// Smooth the front buffer while preserving edges, with a Kuwahara
+    /// filter.
+    ///
+    /// Every pixel is replaced by the mean color of whichever of the four
+    /// `(radius + 1) x (radius + 1)` quadrants around it has the lowest
+    /// variance. Quadrants are cut off at the edges of the buffer.
+    pub fn kuwahara(&mut self, radius: usize) {
+        let width = Renderer::<T>::width(self);
+        let height = Renderer::<T>::height(self);
+        let src = self.buffer().to_vec();
+
+        let quadrant = |x0: usize, x1: usize, y0: usize, y1: usize| {
+            let mut sum = (0.0, 0.0, 0.0);
+            let mut sq = 0.0;
+            for y in y0..y1 + 1 {
+                for &(r, g, b) in &src[y * width + x0..y * width + x1 + 1] {
+                    let (r, g, b) = (f64::from(r), f64::from(g), f64::from(b));
+                    sum = (sum.0 + r, sum.1 + g, sum.2 + b);
+                    sq += r * r + g * g + b * b;
+                }
+            }
+
+            let n = ((x1 - x0 + 1) * (y1 - y0 + 1)) as f64;
+            let mean = (sum.0 / n, sum.1 / n, sum.2 / n);
+            let var = sq / n - (mean.0 * mean.0 + mean.1 * mean.1 + mean.2 * mean.2);
+            (var, mean)
+        };
+
+        for (i, px) in self.buffer_mut().iter_mut().enumerate() {
+            let (x, y) = (i % width, i / width);
+            let (left, right) = (x.saturating_sub(radius), (x + radius).min(width - 1));
+            let (top, bottom) = (y.saturating_sub(radius), (y + radius).min(height - 1));
+
+            let (_, (r, g, b)) = [
+                quadrant(left, x, top, y),
+                quadrant(x, right, top, y),
+                quadrant(left, x, y, bottom),
+                quadrant(x, right, y, bottom),
+            ]
+            .iter()
+            .fold((f64::INFINITY, (0.0, 0.0, 0.0)), |min, &q| {
+                if q.0 < min.0 {
+                    q
+                } else {
+                    min
+                }
+            });
+
+            *px = (r.round() as u8, g.round() as u8, b.round() as u8);
+        }
+    }
+}
+
+/// Blur `buf` in place with a separable gaussian kernel of `radius`,
+/// clamping at the edges.
+fn gaussian_blur(buf: &mut [(f64, f64, f64)], width: usize, height: usize, radius: usize) {
+    if radius == 0 || width == 0 || height == 0 {
+        return;
+    }
+
+    let sigma = radius as f64 / 2.0;
+    let kernel = (0..2 * radius + 1)
+        .map(|i| {
+            let x = i as f64 - radius as f64;
+            (-x * x / (2.0 * sigma * sigma)).exp()
+        })
+        .collect::<Vec<_>>();
+    let sum = kernel.iter().sum::<f64>();
+    let taps = kernel
+        .into_iter()
+        .enumerate()
+        .map(|(i, k)| (i as isize - radius as isize, k / sum))
+        .collect::<Vec<_>>();
+
+    let clamp = |v: isize, len: usize| v.max(0).min(len as isize - 1) as usize;
+    let weigh = |src: &[(f64, f64, f64)], index: &dyn Fn(isize) -> usize| {
+        taps.iter().fold((0.0, 0.0, 0.0), |acc, &(offset, k)| {
+            let (r, g, b) = src[index(offset)];
+            (acc.0 + r * k, acc.1 + g * k, acc.2 + b * k)
+        })
+    };
+
+    let src = buf.to_vec();
+    for y in 0..height {
+        for x in 0..width {
+            let index = |o| y * width + clamp(x as isize + o, width);
+            buf[y * width + x] = weigh(&src, &index);
+        }
+    }
+
+    let src = buf.to_vec();
+    for y in 0..height {
+        for x in 0..width {
+            let index = |o| clamp(y as isize + o, height) * width + x;
+            buf[y * width + x] = weigh(&src, &index);
+        }
+    }
+}
+
+/// One dimensional squared distance transform of the sampled function `f`
+/// into `d`, after Felzenszwalb and Huttenlocher.
+fn distance_1d(f: &[f64], d: &mut [f64]) {
+    let n = f.len();
+    if n == 0 {
+        return;
+    }
+
+    // parabolas forming the lower envelope and the boundaries between them
+    let mut v = vec![0; n];
+    let mut z = vec![0.0; n + 1];
+    let mut k = 0;
+    z[0] = f64::NEG_INFINITY;
+    z[1] = f64::INFINITY;
+
+    for q in 1..n {
+        let fq = f[q] + (q * q) as f64;
+        let s = loop {
+            let p = v[k];
+            let s = (fq - (f[p] + (p * p) as f64)) / (2 * q - 2 * p) as f64;
+            if s <= z[k] && k > 0 {
+                k -= 1;
+            } else {
+                break s;
+            }
+        };
+        k += 1;
+        v[k] = q;
+        z[k] = s;
+        z[k + 1] = f64::INFINITY;
+    }
+
+    k = 0;
+    for (q, d) in d.iter_mut().enumerate() {
+        while z[k + 1] < q as f64 {
+            k += 1;
+        }
+        let dq = q as f64 - v[k] as f64;
+        *d = dq * dq + f[v[k]];
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::iter;
+
+    use point::Point;
+    use renderer::{Renderer, SimpleRenderer};
+
+    #[test]
+    fn distance_field() {
+        let mut renderer = SimpleRenderer::<u8>::new(9, 9);
+        renderer.set_attr(0, 1);
+        assert!(renderer.draw(iter::once(Point((4.0, 4.0)))).is_ok());
+        renderer.swap();
+
+        let field = renderer.distance_field();
+        let at = |x: usize, y: usize| field[y * 9 + x];
+
+        assert_eq!(at(4, 4), 0.0);
+        assert_eq!(at(5, 4), 1.0);
+        assert_eq!(at(4, 2), 2.0);
+        assert!((at(6, 6) - 8.0_f32.sqrt()).abs() < 1e-6);
+        assert!((at(0, 0) - 32.0_f32.sqrt()).abs() < 1e-6);
+
+        for y in 0..9 {
+            for x in 0..9 {
+                let dx = x as f32 - 4.0;
+                let dy = y as f32 - 4.0;
+                assert!((at(x, y) - (dx * dx + dy * dy).sqrt()).abs() < 1e-6);
+            }
+        }
+    }
+
+    #[test]
+    fn bloom() {
+        let mut renderer = SimpleRenderer::<(u8, u8, u8)>::new(9, 9);
+        renderer.set_attr(0, (255, 255, 255));
+        assert!(renderer.draw(iter::once(Point((4.0, 4.0)))).is_ok());
+        renderer.swap();
+
+        renderer.bloom(0.5, 2, 4.0);
+        let at = |x: usize, y: usize| renderer.buffer()[y * 9 + x].0;
+
+        assert_eq!(at(4, 4), 255);
+        assert!(at(5, 4) > 0);
+        assert!(at(5, 4) > at(6, 4));
+        assert!(at(6, 4) > 0);
+        assert!(at(5, 5) > at(6, 6));
+        assert_eq!(at(5, 4), at(3, 4));
+        assert_eq!(at(5, 4), at(4, 5));
+        assert_eq!(at(0, 0), 0);
+    }
+
+    #[test]
+    fn vignette() {
+        let gray = |renderer: &mut SimpleRenderer<(u8, u8, u8)>| {
+            for px in renderer.buffer_mut() {
+                *px = (200, 200, 200);
+            }
+        };
+        let mut renderer = SimpleRenderer::<(u8, u8, u8)>::new(9, 9);
+
+        gray(&mut renderer);
+        renderer.vignette(0.0);
+        assert!(renderer.buffer().iter().all(|&px| px == (200, 200, 200)));
+
+        renderer.vignette(0.5);
+        assert_eq!(renderer.buffer()[4 * 9 + 4], (200, 200, 200));
+        assert_eq!(renderer.buffer()[0], (100, 100, 100));
+        assert_eq!(renderer.buffer()[9 * 9 - 1], (100, 100, 100));
+        assert!(renderer.buffer()[4] > renderer.buffer()[0]);
+
+        gray(&mut renderer);
+        renderer.vignette(0.25);
+        assert_eq!(renderer.buffer()[4 * 9 + 4], (200, 200, 200));
+        assert_eq!(renderer.buffer()[8], (150, 150, 150));
+    }
+
+    #[test]
+    fn chromatic_aberration() {
+        let line = |x: usize| {
+            let mut renderer = SimpleRenderer::<(u8, u8, u8)>::new(17, 5);
+            for (i, px) in renderer.buffer_mut().iter_mut().enumerate() {
+                if i % 17 == x {
+                    *px = (255, 255, 255);
+                }
+            }
+            renderer.chromatic_aberration(0.2);
+            renderer
+        };
+
+        let renderer = line(14);
+        let at = |x: usize| renderer.buffer()[2 * 17 + x];
+        assert_eq!(at(13), (255, 0, 0));
+        assert_eq!(at(14), (0, 255, 0));
+        assert_eq!(at(15), (0, 0, 255));
+        assert_eq!(at(12), (0, 0, 0));
+
+        let renderer = line(8);
+        let at = |x: usize| renderer.buffer()[2 * 17 + x];
+        assert_eq!(at(7), (0, 0, 0));
+        assert_eq!(at(8), (255, 255, 255));
+        assert_eq!(at(9), (0, 0, 0));
+    }
+
+    #[test]
+    fn posterize() {
+        let mut renderer = SimpleRenderer::<(u8, u8, u8)>::new(256, 1);
+        let ramp = |renderer: &mut SimpleRenderer<(u8, u8, u8)>| {
+            for (i, px) in renderer.buffer_mut().iter_mut().enumerate() {
+                *px = (i as u8, i as u8, 255 - i as u8);
+            }
+        };
+
+        ramp(&mut renderer);
+        renderer.posterize(2);
+        assert!(renderer
+            .buffer()
+            .iter()
+            .all(|&(r, g, b)| { [r, g, b].iter().all(|&c| c == 0 || c == 255) }));
+        assert_eq!(renderer.buffer()[0], (0, 0, 255));
+        assert_eq!(renderer.buffer()[255], (255, 255, 0));
+
+        ramp(&mut renderer);
+        renderer.posterize(4);
+        assert!(renderer
+            .buffer()
+            .iter()
+            .all(|&(r, g, b)| { [r, g, b].iter().all(|&c| [0, 85, 170, 255].contains(&c)) }));
+    }
+
+    #[test]
+    fn kuwahara() {
+        const WIDTH: usize = 16;
+        const HEIGHT: usize = 8;
+
+        let mut renderer = SimpleRenderer::<(u8, u8, u8)>::new(WIDTH, HEIGHT);
+        for (i, px) in renderer.buffer_mut().iter_mut().enumerate() {
+            let (x, y) = (i % WIDTH, i / WIDTH);
+            let base = if x < WIDTH / 2 { 100 } else { 200 };
+            let c = base + (x * 7 + y * 13) % 11 - 5;
+            *px = (c as u8, c as u8, c as u8);
+        }
+
+        let variance = |renderer: &SimpleRenderer<(u8, u8, u8)>| {
+            let region = (2..HEIGHT - 2)
+                .flat_map(|y| (2..WIDTH / 2 - 2).map(move |x| (x, y)))
+                .map(|(x, y)| f64::from(renderer.buffer()[y * WIDTH + x].0))
+                .collect::<Vec<_>>();
+            let mean = region.iter().sum::<f64>() / region.len() as f64;
+            region.iter().map(|c| (c - mean) * (c - mean)).sum::<f64>() / region.len() as f64
+        };
+
+        let before = variance(&renderer);
+        renderer.kuwahara(2);
+        assert!(variance(&renderer) < before / 2.0);
+
+        for y in 0..HEIGHT {
+            let left = renderer.buffer()[y * WIDTH + WIDTH / 2 - 1].0;
+            let right = renderer.buffer()[y * WIDTH + WIDTH / 2].0;
+            assert!(left < 110 && right > 190);
+        }
+    }
+}
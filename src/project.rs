@@ -0,0 +1,208 @@
+//! Projections of 3D points to 2D screenspace.
+
+use num_traits::Float;
+
+use point::{Point2, Point3};
+
+/// A projection from a left-handed view space, looking along `+z` with `+y`
+/// up, to normalized device coordinates.
+///
+/// Points within the view volume are mapped to `[-1, 1]` along x and y, with
+/// `+y` up, and their depth is mapped to `[0, 1]` from the near plane to the
+/// far plane.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Projection<T> {
+    m: [[T; 4]; 4],
+}
+
+impl<T: Float> Projection<T> {
+    /// Create an orthographic projection of the box between the given planes.
+    #[inline]
+    pub fn ortho(left: T, right: T, bottom: T, top: T, near: T, far: T) -> Self {
+        let (zero, one) = (T::zero(), T::one());
+        let two = one + one;
+        let (w, h, d) = (right - left, top - bottom, far - near);
+
+        Projection {
+            m: [
+                [two / w, zero, zero, -(right + left) / w],
+                [zero, two / h, zero, -(top + bottom) / h],
+                [zero, zero, one / d, -near / d],
+                [zero, zero, zero, one],
+            ],
+        }
+    }
+
+    /// Create a perspective projection of the frustum with the vertical field
+    /// of view `fov` in radians and the ratio `aspect` of width to height.
+    ///
+    /// `near` and `far` must be positive.
+    #[inline]
+    pub fn perspective(fov: T, aspect: T, near: T, far: T) -> Self {
+        let (zero, one) = (T::zero(), T::one());
+        let f = (fov / (one + one)).tan().recip();
+        let d = far - near;
+
+        Projection {
+            m: [
+                [f / aspect, zero, zero, zero],
+                [zero, f, zero, zero],
+                [zero, zero, far / d, -far * near / d],
+                [zero, zero, one, zero],
+            ],
+        }
+    }
+
+    /// Project `p` to normalized device coordinates.
+    ///
+    /// # Returns
+    ///
+    /// The projected point and its depth, which increases monotonically
+    /// with the distance from the viewer, i.e. for depth testing.
+    #[inline]
+    pub fn project(&self, (x, y, z): Point3<T>) -> (Point2<T>, T) {
+        let row = |r: [T; 4]| r[0] * x + r[1] * y + r[2] * z + r[3];
+        let w_recip = row(self.m[3]).recip();
+
+        (
+            (row(self.m[0]) * w_recip, row(self.m[1]) * w_recip),
+            row(self.m[2]) * w_recip,
+        )
+    }
+
+    /// Whether `p` lies behind the near plane, i.e. outside of the view
+    /// volume on the side of the viewer. Points behind the viewer of a
+    /// perspective projection are behind the near plane too.
+    #[inline]
+    pub fn behind_near(&self, (x, y, z): Point3<T>) -> bool {
+        let r = self.m[2];
+        r[0] * x + r[1] * y + r[2] * z + r[3] < T::zero()
+    }
+
+    /// Project `p` like `project`, then map it onto a screen of the given
+    /// size, with the origin in the top left corner and `+y` down.
+    #[inline]
+    pub fn project_to(&self, p: Point3<T>, width: T, height: T) -> (Point2<T>, T) {
+        let ((x, y), depth) = self.project(p);
+        let half = T::from(0.5).unwrap();
+
+        (
+            ((x + T::one()) * half * width, (T::one() - y) * half * height),
+            depth,
+        )
+    }
+}
+
+/// Clip the triangle `tri` in view space against the near plane at
+/// `z = near`, i.e. before projecting it, so no vertex lies behind the
+/// viewer.
+///
+/// The vertices on the clip boundary are interpolated along the clipped
+/// edges, and the winding of `tri` is kept.
+///
+/// # Returns
+///
+/// - `tri` unchanged if it lies entirely in front of the near plane.
+/// - An empty `Vec` if it lies entirely behind the near plane.
+/// - One or two triangles covering the part in front of the near plane,
+///   if `tri` crosses it.
+pub fn clip_near<T: Float>(tri: [Point3<T>; 3], near: T) -> Vec<[Point3<T>; 3]> {
+    let inside = |p: Point3<T>| p.2 >= near;
+    if tri.iter().all(|&p| inside(p)) {
+        return vec![tri];
+    }
+
+    let mut polygon = Vec::with_capacity(4);
+    for i in 0..3 {
+        let (a, b) = (tri[i], tri[(i + 1) % 3]);
+        if inside(a) {
+            polygon.push(a);
+        }
+        if inside(a) != inside(b) {
+            let t = (near - a.2) / (b.2 - a.2);
+            polygon.push((a.0 + (b.0 - a.0) * t, a.1 + (b.1 - a.1) * t, near));
+        }
+    }
+
+    match polygon.len() {
+        3 => vec![[polygon[0], polygon[1], polygon[2]]],
+        4 => vec![
+            [polygon[0], polygon[1], polygon[2]],
+            [polygon[0], polygon[2], polygon[3]],
+        ],
+        _ => Vec::new(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::f64::consts::FRAC_PI_2;
+
+    use super::{clip_near, Projection};
+
+    #[test]
+    fn near_center() {
+        let perspective = Projection::perspective(FRAC_PI_2, 2.0, 1.0, 100.0);
+        assert_eq!(
+            perspective.project_to((0.0, 0.0, 1.0), 128.0, 64.0),
+            ((64.0, 32.0), 0.0)
+        );
+        let ((x, y), depth) = perspective.project((2.0, 1.0, 100.0));
+        assert!((x - 0.01).abs() < 1e-9);
+        assert!((y - 0.01).abs() < 1e-9);
+        assert!((depth - 1.0).abs() < 1e-9);
+
+        let ortho = Projection::ortho(-2.0, 2.0, -1.0, 1.0, 1.0, 3.0);
+        assert_eq!(
+            ortho.project_to((0.0, 0.0, 1.0), 128.0, 64.0),
+            ((64.0, 32.0), 0.0)
+        );
+        assert_eq!(ortho.project((2.0, -1.0, 3.0)), ((1.0, -1.0), 1.0));
+
+        assert!(!perspective.behind_near((0.0, 0.0, 1.0)));
+        assert!(perspective.behind_near((0.0, 0.0, 0.5)));
+        assert!(perspective.behind_near((0.0, 0.0, -5.0)));
+        assert!(ortho.behind_near((5.0, 5.0, 0.9)));
+    }
+
+    #[test]
+    fn depth_order() {
+        let projections = [
+            Projection::perspective(1.0, 1.0, 0.5, 50.0),
+            Projection::ortho(-1.0, 1.0, -1.0, 1.0, 0.5, 50.0),
+        ];
+
+        for projection in &projections {
+            let depths = (1..100)
+                .map(|z| projection.project((0.3, -0.2, z as f64 * 0.5)).1)
+                .collect::<Vec<_>>();
+            assert!(depths.windows(2).all(|pair| pair[0] < pair[1]));
+        }
+    }
+
+    #[test]
+    fn clip() {
+        let front = [(0.0, 0.0, 2.0), (4.0, 0.0, 1.0), (0.0, 4.0, 3.0)];
+        assert_eq!(clip_near(front, 1.0), [front]);
+
+        let behind = [(0.0, 0.0, 0.5), (4.0, 0.0, -1.0), (0.0, 4.0, 0.9)];
+        assert!(clip_near(behind, 1.0).is_empty());
+
+        // one vertex behind leaves a quad, split in two
+        let one = [(0.0, 0.0, 3.0), (4.0, 0.0, -1.0), (0.0, 4.0, 3.0)];
+        assert_eq!(
+            clip_near(one, 1.0),
+            [
+                [(0.0, 0.0, 3.0), (2.0, 0.0, 1.0), (2.0, 2.0, 1.0)],
+                [(0.0, 0.0, 3.0), (2.0, 2.0, 1.0), (0.0, 4.0, 3.0)],
+            ]
+        );
+
+        // two vertices behind leave a smaller triangle
+        let two = [(0.0, 0.0, 3.0), (4.0, 0.0, -1.0), (0.0, 4.0, -1.0)];
+        assert_eq!(
+            clip_near(two, 1.0),
+            [[(0.0, 0.0, 3.0), (2.0, 0.0, 1.0), (0.0, 2.0, 1.0)]]
+        );
+    }
+}
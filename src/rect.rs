@@ -1,12 +1,24 @@
-use std::ops::Range;
 use std::marker::PhantomData;
+use std::ops::{Add, Mul, Sub};
+use std::slice;
 
-use num_traits::AsPrimitive;
+use line_drawing::FloatNum;
+use num_traits::{AsPrimitive, Float};
 
-use renderer::Drawable;
+use renderer::{Coord, Drawable};
 use point::Point2;
+use line::Line;
+use transform::{Affine2, Transform};
 
-#[derive(Clone, Copy, Debug)]
+/// Primitive drawable filled rectangle between the bounds `x0` and `x1`
+/// along x and `y0` and `y1` along y, which may be given in any order.
+///
+/// Equality compares the bounds as given, so i.e. the rectangles
+/// `Rectangle::new(0, 2, 0, 3)` and `Rectangle::new(2, 0, 3, 0)` aren't
+/// equal, even though they cover the same pixels. Compare their
+/// `bounding_box`-es to ignore the order.
+#[derive(Clone, Copy, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct Rectangle<T> {
     x0: T,
     x1: T,
@@ -21,6 +33,198 @@ impl<T> Rectangle<T> {
     }
 }
 
+impl<T: PartialOrd + Copy + Add<Output = T>> Rectangle<T> {
+    /// Create a rectangle at `(x, y)`, which is `w` wide and `h` high. The
+    /// bounds are ordered like `from_corners`, so a negative size extends
+    /// the rectangle to the left or top of `(x, y)`.
+    #[inline]
+    pub fn from_xywh(x: T, y: T, w: T, h: T) -> Self {
+        Rectangle::from_corners((x, y), (x + w, y + h))
+    }
+}
+
+impl<T: PartialOrd + Copy> Rectangle<T> {
+    /// Shrink the bounds of `self` to lie within the bounds of `clip`. The
+    /// result is ordered, and empty if they don't overlap.
+    #[inline]
+    pub(crate) fn clamp_to(&self, clip: &Rectangle<T>) -> Rectangle<T> {
+        let max = |a: T, b: T| if a > b { a } else { b };
+        let min = |a: T, b: T| if a < b { a } else { b };
+        let x0 = max(min(self.x0, self.x1), min(clip.x0, clip.x1));
+        let x1 = min(max(self.x0, self.x1), max(clip.x0, clip.x1));
+        let y0 = max(min(self.y0, self.y1), min(clip.y0, clip.y1));
+        let y1 = min(max(self.y0, self.y1), max(clip.y0, clip.y1));
+
+        Rectangle {
+            x0,
+            x1: max(x0, x1),
+            y0,
+            y1: max(y0, y1),
+        }
+    }
+
+    /// Get the top left and bottom right corners, regardless of the order
+    /// the bounds were given in.
+    #[inline]
+    pub(crate) fn min_max(&self) -> (Point2<T>, Point2<T>) {
+        let rect = self.clamp_to(self);
+        ((rect.x0, rect.y0), (rect.x1, rect.y1))
+    }
+
+    /// Get the smaller bound along x, i.e. the left edge on screen.
+    #[inline]
+    pub fn min_x(&self) -> T {
+        let ((x0, _), _) = self.min_max();
+        x0
+    }
+
+    /// Get the larger bound along x, i.e. the right edge on screen.
+    #[inline]
+    pub fn max_x(&self) -> T {
+        let (_, (x1, _)) = self.min_max();
+        x1
+    }
+
+    /// Get the smaller bound along y, i.e. the top edge on screen.
+    #[inline]
+    pub fn min_y(&self) -> T {
+        let ((_, y0), _) = self.min_max();
+        y0
+    }
+
+    /// Get the larger bound along y, i.e. the bottom edge on screen.
+    #[inline]
+    pub fn max_y(&self) -> T {
+        let (_, (_, y1)) = self.min_max();
+        y1
+    }
+
+    /// Get the four corners, regardless of the order the bounds were given
+    /// in. They run clockwise on screen from the top left corner, like the
+    /// `edges`: top left, top right, bottom right and bottom left.
+    #[inline]
+    pub fn corners(&self) -> [Point2<T>; 4] {
+        let ((x0, y0), (x1, y1)) = self.min_max();
+        [(x0, y0), (x1, y0), (x1, y1), (x0, y1)]
+    }
+
+    /// Create a rectangle spanning the corners `a` and `b`, which may be
+    /// any two opposite corners, i.e. from a mouse drag in any direction.
+    /// The bounds are ordered, with the top left corner first.
+    #[inline]
+    pub fn from_corners(a: Point2<T>, b: Point2<T>) -> Self {
+        Rectangle::new(a.0, b.0, a.1, b.1).bounding_box()
+    }
+
+    /// Get a copy of the rectangle with ordered bounds, i.e. with the top
+    /// left corner first.
+    #[inline]
+    pub fn bounding_box(&self) -> Rectangle<T> {
+        self.clamp_to(self)
+    }
+
+    /// Whether `p` lies within the half-open bounds of the rectangle, i.e.
+    /// on the left or top edge, but not on the right or bottom edge.
+    #[inline]
+    pub fn contains(&self, p: Point2<T>) -> bool {
+        let within = |v: T, a: T, b: T| {
+            let (lo, hi) = if a < b { (a, b) } else { (b, a) };
+            lo <= v && v < hi
+        };
+
+        within(p.0, self.x0, self.x1) && within(p.1, self.y0, self.y1)
+    }
+
+    /// Get the overlap of `self` and `other`.
+    ///
+    /// # Returns
+    ///
+    /// - `Some(Rectangle)` with ordered bounds if the rectangles overlap.
+    /// - `None` if they are disjoint or only touch along an edge.
+    #[inline]
+    pub fn intersect(&self, other: &Rectangle<T>) -> Option<Rectangle<T>> {
+        let rect = self.clamp_to(other);
+        if rect.x0 < rect.x1 && rect.y0 < rect.y1 {
+            Some(rect)
+        } else {
+            None
+        }
+    }
+}
+
+impl<T: PartialOrd + Copy + Sub<Output = T> + Mul<Output = T>> Rectangle<T> {
+    /// Get the extent along x, regardless of the order the bounds were
+    /// given in.
+    #[inline]
+    pub fn width(&self) -> T {
+        let (min, max) = self.min_max();
+        max.0 - min.0
+    }
+
+    /// Get the extent along y, regardless of the order the bounds were
+    /// given in.
+    #[inline]
+    pub fn height(&self) -> T {
+        let (min, max) = self.min_max();
+        max.1 - min.1
+    }
+
+    /// Get the area, i.e. `width * height`.
+    #[inline]
+    pub fn area(&self) -> T {
+        self.width() * self.height()
+    }
+}
+
+impl<T: Float + AsPrimitive<i64> + 'static> Rectangle<T>
+where
+    i64: AsPrimitive<T>,
+{
+    /// Iterate over the centers of the pixels of the rectangle, i.e. for
+    /// sampling a continuous function once per pixel.
+    #[inline]
+    pub fn sample_points(self) -> impl Iterator<Item = Point2<T>> {
+        let half = T::from(0.5).unwrap();
+        self.into_iter().map(move |(x, y)| (x + half, y + half))
+    }
+}
+
+impl<T: FloatNum> Rectangle<T> {
+    /// Get the outline of the rectangle, i.e. for drawing it as a
+    /// wireframe. The edges run clockwise on screen from the top left
+    /// corner, each one ending where the next one starts.
+    #[inline]
+    pub fn edges(&self) -> [Line<T>; 4] {
+        let c = self.corners();
+        [
+            Line::new(c[0], c[1]),
+            Line::new(c[1], c[2]),
+            Line::new(c[2], c[3]),
+            Line::new(c[3], c[0]),
+        ]
+    }
+}
+
+impl<T: Float> Transform<T> for Rectangle<T> {
+    /// Get the axis-aligned bounding box of the transformed rectangle, as
+    /// a `Rectangle` can't be rotated or skewed.
+    #[inline]
+    fn transform(&self, m: &Affine2<T>) -> Self {
+        let corners = [
+            m.apply((self.x0, self.y0)),
+            m.apply((self.x1, self.y0)),
+            m.apply((self.x0, self.y1)),
+            m.apply((self.x1, self.y1)),
+        ];
+        let (x0, x1, y0, y1) = corners.iter().fold(
+            (T::infinity(), T::neg_infinity(), T::infinity(), T::neg_infinity()),
+            |(x0, x1, y0, y1), &(x, y)| (x0.min(x), x1.max(x), y0.min(y), y1.max(y)),
+        );
+
+        Rectangle::new(x0, x1, y0, y1)
+    }
+}
+
 impl<T: Copy + AsPrimitive<i64> + 'static> Drawable<T, Point2<T>> for Rectangle<T>
 where
     i64: AsPrimitive<T>,
@@ -40,22 +244,29 @@ where
 
     #[inline]
     fn into_iter(self) -> Self::IntoIter {
-        let x0 = self.x0.as_();
-        let x1 = self.x1.as_();
-        let y0 = self.y0.as_();
-        let y1 = self.y1.as_();
-
-        let width = x0..x1;
-        let mut height = y0..y1;
-        let x = width.clone();
-        let y = height.next();
+        let (x0, x1): (i64, i64) = (self.x0.as_(), self.x1.as_());
+        let (y0, y1): (i64, i64) = (self.y0.as_(), self.y1.as_());
+
+        // unordered corners still describe the same half-open rectangle
+        let (x0, x1) = (x0.min(x1), x0.max(x1));
+        let (y0, y1) = (y0.min(y1), y0.max(y1));
+
+        // the size of huge or infinite rectangles saturates
+        let len = if x0 < x1 && y0 < y1 {
+            x1.checked_sub(x0)
+                .and_then(|w| w.checked_mul(y1.checked_sub(y0)?))
+                .map_or(usize::MAX, |len| len as usize)
+        } else {
+            0
+        };
         let _phantom = PhantomData;
 
         IntoIter {
-            width,
-            height,
-            x,
-            y,
+            x0,
+            x1,
+            front: (x0, y0),
+            back: (x1.saturating_sub(1), y1.saturating_sub(1)),
+            len,
             _phantom,
         }
     }
@@ -63,10 +274,15 @@ where
 
 #[derive(Debug)]
 pub struct IntoIter<T> {
-    x: Range<i64>,
-    y: Option<i64>,
-    width: Range<i64>,
-    height: Range<i64>,
+    x0: i64,
+    x1: i64,
+    /// The next pixel yielded by `next`.
+    front: Point2<i64>,
+    /// The next pixel yielded by `next_back`.
+    back: Point2<i64>,
+    /// The count of pixels between `front` and `back`, inclusive, or
+    /// `usize::MAX` if there are more.
+    len: usize,
     _phantom: PhantomData<T>,
 }
 
@@ -78,26 +294,180 @@ where
 
     #[inline]
     fn next(&mut self) -> Option<Self::Item> {
-        let x = self.x.next().or_else(|| {
-            self.height
-                .next()
-                .and_then(|y| {
-                    self.y = Some(y);
-                    self.x = self.width.clone();
-                    self.x.next()
-                })
-                .or_else(|| {
-                    self.y = None;
-                    None
-                })
-        });
+        if self.len == 0 {
+            return None;
+        }
+        self.len -= 1;
+
+        let (x, y) = self.front;
+        self.front = if x + 1 < self.x1 {
+            (x + 1, y)
+        } else {
+            (self.x0, y + 1)
+        };
+
+        Some((x.as_(), y.as_()))
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.len, Some(self.len))
+    }
+}
+
+impl<T: Copy + 'static> DoubleEndedIterator for IntoIter<T>
+where
+    i64: AsPrimitive<T>,
+{
+    #[inline]
+    fn next_back(&mut self) -> Option<Self::Item> {
+        if self.len == 0 {
+            return None;
+        }
+        self.len -= 1;
+
+        let (x, y) = self.back;
+        self.back = if x > self.x0 {
+            (x - 1, y)
+        } else {
+            (self.x1 - 1, y.saturating_sub(1))
+        };
+
+        Some((x.as_(), y.as_()))
+    }
+}
+
+impl<T: Copy + 'static> ExactSizeIterator for IntoIter<T> where i64: AsPrimitive<T> {}
+
+/// Euclidean coordinate and gradient factor in `[0, 1]` on a
+/// `GradientRect`.
+pub type GradientCoord<T> = (Point2<T>, T);
+
+impl<T: Copy> Coord<T> for GradientCoord<T> {
+    #[inline(always)]
+    fn point(&self) -> Point2<T> {
+        self.0
+    }
+
+    /// Get the gradient factor, as the only element.
+    #[inline(always)]
+    fn barycentric(&self) -> Option<&[T]> {
+        Some(slice::from_ref(&self.1))
+    }
+}
+
+/// The direction a `GradientRect` runs in.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum GradientMode {
+    /// From the left to the right column.
+    Horizontal,
+    /// From the top to the bottom row.
+    Vertical,
+    /// From the top left to the bottom right corner.
+    Diagonal,
+}
+
+/// A rectangle, whose pixels carry a factor for a linear gradient, i.e.
+/// for interpolating between two colors in `draw_with`.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct GradientRect<T> {
+    rect: Rectangle<T>,
+    mode: GradientMode,
+}
+
+impl<T> GradientRect<T> {
+    #[inline(always)]
+    pub fn new(rect: Rectangle<T>, mode: GradientMode) -> Self {
+        GradientRect { rect, mode }
+    }
+}
+
+impl<T: Float + AsPrimitive<i64> + 'static> Drawable<T, GradientCoord<T>> for GradientRect<T>
+where
+    i64: AsPrimitive<T>,
+{
+    #[inline(always)]
+    fn vertices(&self) -> usize {
+        4
+    }
+}
+
+impl<T: Float + AsPrimitive<i64> + 'static> IntoIterator for GradientRect<T>
+where
+    i64: AsPrimitive<T>,
+{
+    type Item = GradientCoord<T>;
+    type IntoIter = GradientIter<T>;
+
+    /// Iterate over the pixels of the rectangle like `Rectangle`. The
+    /// factor is 0 in the first and 1 in the last column or row, or 0
+    /// everywhere if there's only one.
+    #[inline]
+    fn into_iter(self) -> Self::IntoIter {
+        let rect = self.rect.into_iter();
+        let first = rect.front;
+        let last = rect.back;
+        let recip = |a: i64, b: i64| {
+            if b > a {
+                (AsPrimitive::<T>::as_(b) - a.as_()).recip()
+            } else {
+                T::zero()
+            }
+        };
+
+        GradientIter {
+            first: (first.0.as_(), first.1.as_()),
+            scale: (recip(first.0, last.0), recip(first.1, last.1)),
+            mode: self.mode,
+            rect,
+        }
+    }
+}
+
+/// An iterator over the pixels of a rectangle and their gradient factor.
+///
+/// See `GradientRect`.
+#[derive(Debug)]
+pub struct GradientIter<T> {
+    rect: IntoIter<T>,
+    /// The top left pixel.
+    first: Point2<T>,
+    /// The reciprocal of the distance between the first and the last
+    /// column and row.
+    scale: Point2<T>,
+    mode: GradientMode,
+}
+
+impl<T: Float + 'static> Iterator for GradientIter<T>
+where
+    i64: AsPrimitive<T>,
+{
+    type Item = GradientCoord<T>;
+
+    #[inline]
+    fn next(&mut self) -> Option<Self::Item> {
+        let (x, y) = self.rect.next()?;
+        let fx = (x - self.first.0) * self.scale.0;
+        let fy = (y - self.first.1) * self.scale.1;
+        let f = match self.mode {
+            GradientMode::Horizontal => fx,
+            GradientMode::Vertical => fy,
+            GradientMode::Diagonal => (fx + fy) / T::from(2.0).unwrap(),
+        };
 
-        x.and_then(|x| self.y.map(|y| (x.as_(), y.as_())))
+        Some(((x, y), f))
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.rect.size_hint()
     }
 }
 
 #[cfg(test)]
 mod tests {
+    use std::collections::BTreeSet;
+
     use super::Rectangle;
 
     #[test]
@@ -107,4 +477,216 @@ mod tests {
             [(0, 0), (1, 0), (0, 1), (1, 1),]
         )
     }
+
+    #[test]
+    fn len() {
+        let mut iter = Rectangle::new(0, 4, 0, 3).into_iter();
+        assert_eq!(iter.len(), 12);
+
+        for remaining in (0..12).rev() {
+            assert!(iter.next().is_some());
+            assert_eq!(iter.len(), remaining);
+        }
+        assert!(iter.next().is_none());
+        assert_eq!(iter.len(), 0);
+
+        assert_eq!(Rectangle::new(0, 0, 0, 3).into_iter().len(), 0);
+    }
+
+    #[test]
+    fn huge() {
+        use std::f64;
+
+        use super::{GradientMode, GradientRect};
+
+        let rect = Rectangle::new(-1e300, 1e300, 0.0, 1.0);
+        assert_eq!(rect.into_iter().len(), usize::MAX);
+        assert_eq!(rect.into_iter().next(), Some((i64::MIN as f64, 0.0)));
+        assert_eq!(rect.into_iter().next_back(), Some((i64::MAX as f64 - 1.0, 0.0)));
+
+        let inf = Rectangle::new(f64::NEG_INFINITY, f64::INFINITY, 0.0, f64::INFINITY);
+        assert_eq!(inf.into_iter().len(), usize::MAX);
+        assert!(GradientRect::new(inf, GradientMode::Diagonal).into_iter().next().is_some());
+
+        // both corners saturate to the same bound
+        let empty = Rectangle::new(f64::NEG_INFINITY, -1e300, 0.0, 1.0);
+        assert_eq!(empty.into_iter().len(), 0);
+        assert!(empty.into_iter().next_back().is_none());
+
+        let mut row = Rectangle::new(0.0, 2.0, f64::NEG_INFINITY, -1e300).into_iter();
+        assert!(row.next_back().is_none());
+        let mut row = Rectangle::new(0, 2, i64::MIN, i64::MIN + 1).into_iter();
+        assert_eq!(row.next_back(), Some((1, i64::MIN)));
+        assert_eq!(row.next_back(), Some((0, i64::MIN)));
+        assert!(row.next_back().is_none());
+    }
+
+    #[test]
+    fn eq() {
+        assert_eq!(Rectangle::new(0, 4, 0, 4), Rectangle::new(0, 4, 0, 4));
+        assert_ne!(Rectangle::new(0, 4, 0, 4), Rectangle::new(0, 4, 0, 5));
+    }
+
+    #[test]
+    fn from_corners() {
+        let rect = Rectangle::new(1.0, 4.0, 2.0, 6.0);
+        assert_eq!(Rectangle::from_corners((1.0, 2.0), (4.0, 6.0)), rect);
+        assert_eq!(Rectangle::from_xywh(1.0, 2.0, 3.0, 4.0), rect);
+
+        // dragged from the bottom right to the top left
+        assert_eq!(Rectangle::from_corners((4.0, 6.0), (1.0, 2.0)), rect);
+        assert_eq!(Rectangle::from_corners((1.0, 6.0), (4.0, 2.0)), rect);
+        assert_eq!(Rectangle::from_xywh(4.0, 6.0, -3.0, -4.0), rect);
+        assert_eq!(Rectangle::from_corners((4, 6), (1, 2)).into_iter().len(), 12);
+    }
+
+    #[test]
+    fn measure() {
+        let rect = Rectangle::new(1.0, 4.0, 2.0, 7.0);
+        assert_eq!((rect.width(), rect.height()), (3.0, 5.0));
+        assert_eq!(rect.area(), rect.width() * rect.height());
+
+        let flipped = Rectangle::new(4.0, 1.0, 7.0, 2.0);
+        assert_eq!((flipped.width(), flipped.height()), (3.0, 5.0));
+        assert_eq!(flipped.area(), 15.0);
+
+        assert_eq!(Rectangle::new(2_usize, 0, 5, 1).area(), 8);
+    }
+
+    #[test]
+    fn bounds() {
+        let rect = Rectangle::new(0, 2, 0, 3);
+        assert_eq!(rect.corners(), [(0, 0), (2, 0), (2, 3), (0, 3)]);
+
+        let flipped = Rectangle::new(2.0, 0.0, 3.0, 0.0);
+        assert_eq!((flipped.min_x(), flipped.max_x()), (0.0, 2.0));
+        assert_eq!((flipped.min_y(), flipped.max_y()), (0.0, 3.0));
+        assert_eq!(
+            flipped.corners(),
+            [(0.0, 0.0), (2.0, 0.0), (2.0, 3.0), (0.0, 3.0)]
+        );
+    }
+
+    #[test]
+    fn contains() {
+        let rect = Rectangle::new(4.0, 0.0, 0.0, 2.0);
+        assert!(rect.contains((0.0, 0.0)));
+        assert!(rect.contains((3.5, 1.5)));
+        assert!(!rect.contains((4.0, 1.0)));
+        assert!(!rect.contains((1.0, 2.0)));
+        assert!(!rect.contains((-0.5, 1.0)));
+    }
+
+    #[test]
+    fn intersect() {
+        let bounds = |r: Rectangle<i64>| (r.x0, r.x1, r.y0, r.y1);
+        let rect = Rectangle::new(0, 4, 0, 4);
+
+        let full = rect.intersect(&Rectangle::new(1, 3, 1, 3)).map(bounds);
+        assert_eq!(full, Some((1, 3, 1, 3)));
+        let partial = rect.intersect(&Rectangle::new(6, 2, 5, 3)).map(bounds);
+        assert_eq!(partial, Some((2, 4, 3, 4)));
+
+        assert!(rect.intersect(&Rectangle::new(4, 6, 0, 4)).is_none());
+        assert!(rect.intersect(&Rectangle::new(0, 4, -2, 0)).is_none());
+        assert!(rect.intersect(&Rectangle::new(5, 6, 5, 6)).is_none());
+    }
+
+    #[test]
+    fn edges() {
+        let pixels = Rectangle::new(3.0, 0.0, 2.0, 0.0)
+            .edges()
+            .iter()
+            .flat_map(|edge| edge.into_iter())
+            .map(|((x, y), _)| (x as i64, y as i64))
+            .collect::<BTreeSet<_>>();
+        let expected = [
+            (0, 0),
+            (1, 0),
+            (2, 0),
+            (3, 0),
+            (0, 1),
+            (3, 1),
+            (0, 2),
+            (1, 2),
+            (2, 2),
+            (3, 2),
+        ];
+
+        assert_eq!(pixels, expected.iter().cloned().collect());
+    }
+
+    #[test]
+    fn rev() {
+        let mut forward = Rectangle::new(0, 4, 0, 3).into_iter().collect::<Vec<_>>();
+        forward.reverse();
+        assert_eq!(Rectangle::new(0, 4, 0, 3).into_iter().rev().collect::<Vec<_>>(), forward);
+
+        // meeting in the middle yields every pixel exactly once
+        for rect in &[Rectangle::new(0, 3, 0, 3), Rectangle::new(0, 4, 0, 2)] {
+            let mut iter = rect.into_iter();
+            let mut pixels = Vec::new();
+            while let Some(p) = iter.next() {
+                pixels.push(p);
+                pixels.extend(iter.next_back());
+            }
+            pixels.sort();
+            let mut expected = rect.into_iter().collect::<Vec<_>>();
+            expected.sort();
+            assert_eq!(pixels, expected);
+        }
+    }
+
+    #[test]
+    fn unordered() {
+        assert_eq!(
+            Rectangle::new(2, 0, 2, 0).into_iter().collect::<Vec<_>>(),
+            Rectangle::new(0, 2, 0, 2).into_iter().collect::<Vec<_>>()
+        );
+        assert_eq!(
+            Rectangle::new(0, 2, 3, 1).into_iter().collect::<Vec<_>>(),
+            [(0, 1), (1, 1), (0, 2), (1, 2)]
+        );
+    }
+
+    #[test]
+    fn gradient() {
+        use super::{GradientMode, GradientRect};
+
+        let rect = Rectangle::new(2.0, 7.0, 1.0, 4.0);
+        let factors = |mode| {
+            GradientRect::new(rect, mode)
+                .into_iter()
+                .map(|((x, y), f)| ((x as i64, y as i64), f))
+                .collect::<Vec<_>>()
+        };
+
+        for ((x, _), f) in factors(GradientMode::Horizontal) {
+            assert!((f - (x - 2) as f64 / 4.0).abs() < 1e-9);
+        }
+        for ((_, y), f) in factors(GradientMode::Vertical) {
+            assert!((f - (y - 1) as f64 / 2.0).abs() < 1e-9);
+        }
+
+        let diagonal = factors(GradientMode::Diagonal);
+        assert_eq!(diagonal[0], ((2, 1), 0.0));
+        assert_eq!(diagonal[diagonal.len() - 1], ((6, 3), 1.0));
+
+        let column = Rectangle::new(0.0, 1.0, 0.0, 3.0);
+        let factors = GradientRect::new(column, GradientMode::Horizontal)
+            .into_iter()
+            .map(|(_, f)| f)
+            .collect::<Vec<_>>();
+        assert_eq!(factors, [0.0; 3]);
+    }
+
+    #[test]
+    fn sample_points() {
+        assert_eq!(
+            Rectangle::new(0.0, 2.0, 0.0, 2.0)
+                .sample_points()
+                .collect::<Vec<_>>(),
+            [(0.5, 0.5), (1.5, 0.5), (0.5, 1.5), (1.5, 1.5)]
+        )
+    }
 }
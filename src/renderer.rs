@@ -1,10 +1,13 @@
 //! Traits for rendering generic and arbitrary meshes and primitives.
 
-use std::mem;
+use std::marker::PhantomData;
+use std::{error, fmt, mem};
 
-use num_traits::{AsPrimitive, Signed};
+use num_traits::{AsPrimitive, Float, Signed};
 
 use point::Point2;
+use rect::Rectangle;
+use tri::{Triangle, Winding};
 
 /// A trait for types, which can represent a screenspace point and a local
 /// barycentric point.
@@ -25,6 +28,17 @@ pub trait Coord<T> {
     fn barycentric(&self) -> Option<&[T]> {
         None
     }
+
+    /// Get the depth, where smaller values are nearer to the viewer.
+    ///
+    /// # Returns
+    ///
+    /// - `None` if `Self` doesn't have a depth.
+    /// - `Some(z)` if `Self` has a depth, which `draw` tests against the
+    ///   depth buffer.
+    fn depth(&self) -> Option<T> {
+        None
+    }
 }
 
 /// A trait for primitive types, which can be drawn
@@ -33,10 +47,41 @@ pub trait Coord<T> {
 ///
 /// - `T` represents the primitive numeric type used in base computation.
 /// - `C` represents the euclidean/barycentric coordinate returned by this shapes
-/// `IntoIter`.
+///   `IntoIter`.
 pub trait Drawable<T, C: Coord<T>>: IntoIterator<Item = C> {
     /// The count of vertices this `Drawable` has
     fn vertices(&self) -> usize;
+
+    /// Iterate over the fragments like `into_iter`, skipping those outside
+    /// of a `width` by `height` viewport early if possible. The renderer
+    /// drops the remaining ones outside of it one by one, which is the
+    /// default.
+    #[allow(unused_variables)]
+    #[inline(always)]
+    fn into_iter_viewport(self, width: usize, height: usize) -> Self::IntoIter
+    where
+        Self: Sized,
+    {
+        self.into_iter()
+    }
+}
+
+/// Debug+statistics information about a finished `draw`.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct Stats {
+    /// The count of shapes drawn.
+    pub shapes: usize,
+    /// The count of vertices of the many `shapes`.
+    pub vertices: usize,
+    /// The count of fragments put to the screen.
+    pub fragments: usize,
+}
+
+impl From<Stats> for (usize, usize, usize) {
+    #[inline(always)]
+    fn from(stats: Stats) -> Self {
+        (stats.shapes, stats.vertices, stats.fragments)
+    }
 }
 
 /// A trait for types, which can be used to draw meshes and primitives
@@ -44,7 +89,7 @@ pub trait Drawable<T, C: Coord<T>>: IntoIterator<Item = C> {
 /// # Parameters
 ///
 /// - `T` represents the primitive numeric type used in base computation.
-pub trait Renderer<T: Signed + AsPrimitive<usize>> {
+pub trait Renderer<T: Signed + PartialOrd + AsPrimitive<usize>> {
     /// The pixel type that gets drawn to the buffer.
     type Pixel;
     /// The auxilliary attribute type. Can represent i.e. geometry normal or
@@ -55,6 +100,15 @@ pub trait Renderer<T: Signed + AsPrimitive<usize>> {
 
     /// Put `px` at coordinate `p`.
     fn put_pixel(&mut self, p: Point2<usize>, px: Self::Pixel);
+    /// Get the pixel at coordinate `p` of the buffer draws are written to.
+    ///
+    /// # Returns
+    ///
+    /// - `Some(Self::Pixel)` if `p` lies within the buffer.
+    /// - `None` if `p` lies outside of the buffer, or if `Self` can't read
+    ///   pixels back at all.
+    fn get_pixel(&self, p: Point2<usize>) -> Option<Self::Pixel>;
+
     /// Swap back and front buffers.
     fn swap(&mut self);
     /// Get the width of the buffer.
@@ -62,6 +116,20 @@ pub trait Renderer<T: Signed + AsPrimitive<usize>> {
     /// Get the height of the buffer.
     fn height(&self) -> usize;
 
+    /// Fill the buffer draws are written to with `px`, i.e. before drawing
+    /// a new frame.
+    ///
+    /// The default implementation puts `px` at every coordinate.
+    fn clear(&mut self, px: Self::Pixel)
+    where
+        Self::Pixel: Clone,
+    {
+        let width = self.width();
+        for i in 0..width * self.height() {
+            self.put_pixel((i % width, i / width), px.clone());
+        }
+    }
+
     /// Get the n'th attribute.
     /// 
     /// # Returns
@@ -77,8 +145,49 @@ pub trait Renderer<T: Signed + AsPrimitive<usize>> {
     #[allow(unused_variables)]
     fn set_attr(&mut self, attr: usize, val: Self::Attr) {}
 
+    /// Test the depth `z` of a fragment at `p` against the depth buffer and
+    /// update the depth buffer if the test passes.
+    ///
+    /// # Returns
+    ///
+    /// - `true` if the fragment should be drawn. The default implementation
+    ///   always returns `true`, i.e. `Self` has no depth buffer.
+    /// - `false` if the fragment is occluded and should be discarded.
+    #[allow(unused_variables)]
+    fn depth_test(&mut self, p: Point2<usize>, z: T) -> bool {
+        true
+    }
+
+    /// Test the fragment at `p` against the stencil buffer, i.e. for
+    /// drawing only within a mask. Done before `depth_test`, so discarded
+    /// fragments don't update the depth buffer.
+    ///
+    /// # Returns
+    ///
+    /// - `true` if the fragment should be drawn. The default implementation
+    ///   always returns `true`, i.e. `Self` has no stencil buffer.
+    /// - `false` if the fragment is masked and should be discarded.
+    #[allow(unused_variables)]
+    fn stencil_test(&self, p: Point2<usize>) -> bool {
+        true
+    }
+
+    /// Clamp an attribute into the range representable by `Self::Pixel`
+    /// before `draw` converts it. Override this if `Self::Attr` can exceed
+    /// that range, so that i.e. over-range colors saturate instead of
+    /// wrapping around.
+    ///
+    /// The default implementation returns `attr` unchanged.
+    #[inline(always)]
+    fn clamp_attr(&self, attr: Self::Attr) -> Self::Attr {
+        attr
+    }
+
     /// Draw the `mesh` (i.e. a `Drawable`) with previously set attributes.
     ///
+    /// Every fragment is drawn with the 0th attribute. If it isn't set, the
+    /// fragments are still tested and counted, but no pixels are put.
+    ///
     /// # Parameters
     ///
     /// - `I` represents an `Iterator` over primitives, a.k.a. a mesh.
@@ -90,39 +199,630 @@ pub trait Renderer<T: Signed + AsPrimitive<usize>> {
     /// This function returns a `Result` to represent the possibility of
     /// failure. The `Ok` variant contains debug+statistics information.
     ///
-    /// - `Ok((shapes, vertices, fragments))` `shapes` is the count of shapes
-    /// drawn. `vertices` is the count of vertices of the many `shapes`.
-    /// `fragments` is the count of fragments put to the screen.
+    /// - `Ok(Stats)` with the counts of shapes, vertices and fragments drawn.
     /// - `Err(Self::Error)` if something went bad.
     fn draw<C: Coord<T>, D: Drawable<T, C>, I: Iterator<Item = D>>(
         &mut self,
         mesh: I,
-    ) -> Result<(usize, usize, usize), Self::Error> {
-        let width = self.width();
-        let height = self.height();
-        let result = mesh.fold((0, 0, 0), |(shapes, verts, frags), drawable| {
-            let verts = verts + drawable.vertices();
-            let frags = frags
-                + drawable
-                    .into_iter()
-                    .filter_map(|c| {
-                        let (x, y) = c.point();
-                        if x.is_positive() && x.as_() < width && y.is_positive() && y.as_() < height
-                        {
-                            Some((x.as_(), y.as_()))
-                        } else {
-                            None
+    ) -> Result<Stats, Self::Error> {
+        Ok(rasterize(self, mesh, |renderer, _| {
+            Some(renderer.get_attr(0).map(|attr| renderer.clamp_attr(attr).into()))
+        }))
+    }
+
+    /// Draw the `mesh` (i.e. a `Drawable`), computing the color of every
+    /// fragment with `shade`, a.k.a. a fragment shader.
+    ///
+    /// # Parameters
+    ///
+    /// - `I` represents an `Iterator` over primitives, a.k.a. a mesh.
+    /// - `D` represents the type of the primitive returned by `I`.
+    /// - `C` represents the `Coordinate` type of the `Drawable`.
+    /// - `F` represents the shader. It's called with the `Coordinate` of
+    ///   every fragment inside the buffer and returns its color, or `None`
+    ///   to discard the fragment.
+    ///
+    /// # Returns
+    ///
+    /// Same as `draw`. Discarded fragments aren't counted.
+    fn draw_with<C, D, I, F>(&mut self, mesh: I, mut shade: F) -> Result<Stats, Self::Error>
+    where
+        C: Coord<T>,
+        D: Drawable<T, C>,
+        I: Iterator<Item = D>,
+        F: FnMut(&C) -> Option<Self::Pixel>,
+    {
+        Ok(rasterize(self, mesh, |_, c| shade(c).map(Some)))
+    }
+
+    /// Count the shapes, vertices and fragments `draw` would report for the
+    /// `mesh` without drawing it, i.e. to size buffers or for profiling.
+    ///
+    /// Fragments are counted like `draw` counts them, except that
+    /// `depth_test` isn't run, as the depth buffer can't be updated. The
+    /// fragments it would discard are counted too, so for meshes with depth
+    /// the count is only an upper bound of what `draw` reports, i.e.
+    /// occluded fragments are counted.
+    fn measure<C, D, I>(&self, mesh: I) -> Stats
+    where
+        C: Coord<T>,
+        D: Drawable<T, C>,
+        I: Iterator<Item = D>,
+    {
+        let (width, height) = (self.width(), self.height());
+        mesh.fold(Stats::default(), |stats, drawable| {
+            let vertices = stats.vertices + drawable.vertices();
+            let fragments = stats.fragments
+                + visible(drawable, width, height)
+                    .filter(|&(p, _)| self.stencil_test(p))
+                    .count();
+
+            Stats {
+                shapes: stats.shapes + 1,
+                vertices,
+                fragments,
+            }
+        })
+    }
+
+    /// Draw the triangles of the `mesh` like `draw`, skipping the back
+    /// facing ones, i.e. those not ordered in the `front` winding.
+    ///
+    /// # Returns
+    ///
+    /// Same as `draw`. Skipped triangles aren't counted.
+    fn draw_culled<I: Iterator<Item = Triangle<T>>>(
+        &mut self,
+        mesh: I,
+        front: Winding,
+    ) -> Result<Stats, Self::Error>
+    where
+        T: Float + AsPrimitive<i64>,
+        i64: AsPrimitive<T>,
+    {
+        self.draw(mesh.filter(|tri| tri.is_front_facing(front)))
+    }
+}
+
+/// Iterate over the coordinates of `drawable` within a `width` by `height`
+/// buffer, together with their pixel.
+fn visible<T, C, D>(
+    drawable: D,
+    width: usize,
+    height: usize,
+) -> impl Iterator<Item = (Point2<usize>, C)>
+where
+    T: Signed + PartialOrd + AsPrimitive<usize>,
+    C: Coord<T>,
+    D: Drawable<T, C>,
+{
+    drawable.into_iter_viewport(width, height).filter_map(move |c| {
+        let (x, y) = c.point();
+        let zero = T::zero();
+        if x >= zero && x.as_() < width && y >= zero && y.as_() < height {
+            Some(((x.as_(), y.as_()), c))
+        } else {
+            None
+        }
+    })
+}
+
+/// Draw the `mesh` with `renderer`, like `Renderer::draw_with`.
+///
+/// `shade` returns `None` to discard a fragment, or `Some(None)` to test
+/// and count it without putting a pixel, like `draw` without an attribute.
+fn rasterize<T, R, C, D, I, F>(renderer: &mut R, mesh: I, mut shade: F) -> Stats
+where
+    T: Signed + PartialOrd + AsPrimitive<usize>,
+    R: Renderer<T> + ?Sized,
+    C: Coord<T>,
+    D: Drawable<T, C>,
+    I: Iterator<Item = D>,
+    F: FnMut(&R, &C) -> Option<Option<R::Pixel>>,
+{
+    let width = renderer.width();
+    let height = renderer.height();
+    mesh.fold(Stats::default(), |stats, drawable| {
+        let vertices = stats.vertices + drawable.vertices();
+        let fragments = stats.fragments
+            + visible(drawable, width, height)
+                .fold(0, |frags, (p, c)| {
+                    let px = match shade(renderer, &c) {
+                        Some(px) => px,
+                        None => return frags,
+                    };
+                    if !renderer.stencil_test(p) {
+                        return frags;
+                    }
+                    if let Some(z) = c.depth() {
+                        if !renderer.depth_test(p, z) {
+                            return frags;
                         }
-                    })
-                    .fold(0, |frags, p| {
-                        self.get_attr(0).map(|attr| self.put_pixel(p, attr.into()));
-                        frags + 1
-                    });
+                    }
+                    if let Some(px) = px {
+                        renderer.put_pixel(p, px);
+                    }
+                    frags + 1
+                });
+
+        Stats {
+            shapes: stats.shapes + 1,
+            vertices,
+            fragments,
+        }
+    })
+}
+
+/// The errors `SimpleRenderer` can report.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum RenderError {
+    /// A strict `SimpleRenderer` was asked to draw a mesh without any shapes.
+    EmptyMesh,
+}
+
+impl fmt::Display for RenderError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            RenderError::EmptyMesh => write!(f, "mesh is empty"),
+        }
+    }
+}
+
+impl error::Error for RenderError {}
+
+/// How `SimpleRenderer` combines a drawn pixel with the pixel already in the
+/// buffer.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum BlendMode {
+    /// Overwrite the destination pixel.
+    Replace,
+    /// Composite the source pixel over the destination pixel.
+    Over,
+    /// Add the source pixel to the destination pixel, i.e. to accumulate
+    /// light in a floating point buffer or for glows.
+    Add,
+    /// Multiply the destination pixel by the source pixel, i.e. for shadows
+    /// and tints.
+    Multiply,
+}
+
+/// How `SimpleRenderer` compares the stencil value of a pixel against a
+/// reference value to decide whether it's drawn.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum StencilFunc {
+    /// Always draw, i.e. ignore the stencil buffer.
+    Always,
+    /// Never draw.
+    Never,
+    /// Draw if the stencil value equals the reference.
+    Equal(u8),
+    /// Draw if the stencil value differs from the reference.
+    NotEqual(u8),
+    /// Draw if the stencil value is less than the reference.
+    Less(u8),
+    /// Draw if the stencil value is greater than the reference.
+    Greater(u8),
+}
+
+impl StencilFunc {
+    /// Whether a pixel with the stencil value `val` passes the test.
+    #[inline(always)]
+    pub fn test(self, val: u8) -> bool {
+        match self {
+            StencilFunc::Always => true,
+            StencilFunc::Never => false,
+            StencilFunc::Equal(r) => val == r,
+            StencilFunc::NotEqual(r) => val != r,
+            StencilFunc::Less(r) => val < r,
+            StencilFunc::Greater(r) => val > r,
+        }
+    }
+}
+
+/// A trait for pixel types, which can be alpha blended.
+pub trait Blend {
+    /// Composite `self` over `dst`, a.k.a. source-over.
+    fn blend(self, dst: Self) -> Self;
+
+    /// Add `self` to `dst` channel by channel.
+    fn add(self, dst: Self) -> Self;
+
+    /// Multiply `dst` by `self` channel by channel, with channels
+    /// normalized to `[0, 1]`.
+    fn multiply(self, dst: Self) -> Self;
+}
+
+/// Multiply two `u8` channels as if normalized to `[0, 1]`, rounding the
+/// result.
+#[inline(always)]
+fn mul_channel(a: u8, b: u8) -> u8 {
+    ((u16::from(a) * u16::from(b) + 127) / 255) as u8
+}
+
+impl Blend for (u8, u8, u8, u8) {
+    fn blend(self, dst: Self) -> Self {
+        let sa = f64::from(self.3) / 255.0;
+        let da = f64::from(dst.3) / 255.0 * (1.0 - sa);
+        let a = sa + da;
+        if a == 0.0 {
+            return (0, 0, 0, 0);
+        }
+
+        let channel = |s: u8, d: u8| ((f64::from(s) * sa + f64::from(d) * da) / a).round() as u8;
+        (
+            channel(self.0, dst.0),
+            channel(self.1, dst.1),
+            channel(self.2, dst.2),
+            (a * 255.0).round() as u8,
+        )
+    }
+
+    /// The channels saturate at 255.
+    #[inline]
+    fn add(self, dst: Self) -> Self {
+        (
+            self.0.saturating_add(dst.0),
+            self.1.saturating_add(dst.1),
+            self.2.saturating_add(dst.2),
+            self.3.saturating_add(dst.3),
+        )
+    }
+
+    #[inline]
+    fn multiply(self, dst: Self) -> Self {
+        (
+            mul_channel(self.0, dst.0),
+            mul_channel(self.1, dst.1),
+            mul_channel(self.2, dst.2),
+            mul_channel(self.3, dst.3),
+        )
+    }
+}
+
+/// Without an alpha channel the source is opaque, so `blend` just returns
+/// it. `add` doesn't clamp, so channels can exceed 1, see `tone_map`.
+impl Blend for (f32, f32, f32) {
+    #[inline(always)]
+    fn blend(self, _dst: Self) -> Self {
+        self
+    }
+
+    #[inline]
+    fn add(self, dst: Self) -> Self {
+        (self.0 + dst.0, self.1 + dst.1, self.2 + dst.2)
+    }
+
+    #[inline]
+    fn multiply(self, dst: Self) -> Self {
+        (self.0 * dst.0, self.1 * dst.1, self.2 * dst.2)
+    }
+}
+
+/// A trait for pixel types, whose channels can be interpolated, i.e. for
+/// shading a triangle with vertex colors in `draw_with`.
+///
+/// # Parameters
+///
+/// - `T` represents the primitive numeric type of the weights.
+pub trait Pixel<T>: Sized {
+    /// Interpolate `a`, `b` and `c` with the barycentric weights `w`.
+    fn lerp3(a: Self, b: Self, c: Self, w: [T; 3]) -> Self;
+
+    /// Multiply every channel by `s`.
+    fn scale(self, s: T) -> Self;
+}
+
+/// Round `v` to the nearest value representable by a `u8` channel.
+#[inline(always)]
+fn to_channel<T: Float>(v: T) -> u8 {
+    v.round()
+        .max(T::zero())
+        .min(T::from(255.0).unwrap())
+        .to_u8()
+        .unwrap_or(0)
+}
+
+/// Interpolate a single channel with the barycentric weights `w`.
+#[inline(always)]
+fn lerp_channel<T: Float>(a: u8, b: u8, c: u8, w: [T; 3]) -> u8 {
+    let f = |v: u8| T::from(v).unwrap();
+    to_channel(f(a) * w[0] + f(b) * w[1] + f(c) * w[2])
+}
+
+impl<T: Float> Pixel<T> for (u8, u8, u8) {
+    #[inline]
+    fn lerp3(a: Self, b: Self, c: Self, w: [T; 3]) -> Self {
+        (
+            lerp_channel(a.0, b.0, c.0, w),
+            lerp_channel(a.1, b.1, c.1, w),
+            lerp_channel(a.2, b.2, c.2, w),
+        )
+    }
+
+    #[inline]
+    fn scale(self, s: T) -> Self {
+        let f = |v: u8| to_channel(T::from(v).unwrap() * s);
+        (f(self.0), f(self.1), f(self.2))
+    }
+}
+
+impl<T: Float> Pixel<T> for (u8, u8, u8, u8) {
+    #[inline]
+    fn lerp3(a: Self, b: Self, c: Self, w: [T; 3]) -> Self {
+        (
+            lerp_channel(a.0, b.0, c.0, w),
+            lerp_channel(a.1, b.1, c.1, w),
+            lerp_channel(a.2, b.2, c.2, w),
+            lerp_channel(a.3, b.3, c.3, w),
+        )
+    }
+
+    #[inline]
+    fn scale(self, s: T) -> Self {
+        let f = |v: u8| to_channel(T::from(v).unwrap() * s);
+        (f(self.0), f(self.1), f(self.2), f(self.3))
+    }
+}
+
+/// Unlike the `u8` pixels, the channels are neither rounded nor clamped.
+impl<T: Float> Pixel<T> for (f32, f32, f32) {
+    #[inline]
+    fn lerp3(a: Self, b: Self, c: Self, w: [T; 3]) -> Self {
+        let w = [
+            w[0].to_f32().unwrap_or(0.0),
+            w[1].to_f32().unwrap_or(0.0),
+            w[2].to_f32().unwrap_or(0.0),
+        ];
+        let f = |a: f32, b: f32, c: f32| a * w[0] + b * w[1] + c * w[2];
+        (f(a.0, b.0, c.0), f(a.1, b.1, c.1), f(a.2, b.2, c.2))
+    }
+
+    #[inline]
+    fn scale(self, s: T) -> Self {
+        let s = s.to_f32().unwrap_or(0.0);
+        (self.0 * s, self.1 * s, self.2 * s)
+    }
+}
+
+/// An RGBA color with 8 bit channels, i.e. for 24 bit images with alpha.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub struct ColorU8 {
+    /// The red channel.
+    pub r: u8,
+    /// The green channel.
+    pub g: u8,
+    /// The blue channel.
+    pub b: u8,
+    /// The alpha channel, i.e. the opacity.
+    pub a: u8,
+}
+
+impl ColorU8 {
+    /// Create a color from its channels.
+    #[inline(always)]
+    pub fn new(r: u8, g: u8, b: u8, a: u8) -> Self {
+        ColorU8 { r, g, b, a }
+    }
+}
+
+/// An RGBA color with floating point channels, nominally in `[0, 1]`.
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct ColorF32 {
+    /// The red channel.
+    pub r: f32,
+    /// The green channel.
+    pub g: f32,
+    /// The blue channel.
+    pub b: f32,
+    /// The alpha channel, i.e. the opacity.
+    pub a: f32,
+}
+
+impl ColorF32 {
+    /// Create a color from its channels.
+    #[inline(always)]
+    pub fn new(r: f32, g: f32, b: f32, a: f32) -> Self {
+        ColorF32 { r, g, b, a }
+    }
+}
+
+/// Opaque color.
+impl From<(u8, u8, u8)> for ColorU8 {
+    #[inline(always)]
+    fn from((r, g, b): (u8, u8, u8)) -> Self {
+        ColorU8::new(r, g, b, 255)
+    }
+}
+
+impl From<(u8, u8, u8, u8)> for ColorU8 {
+    #[inline(always)]
+    fn from((r, g, b, a): (u8, u8, u8, u8)) -> Self {
+        ColorU8::new(r, g, b, a)
+    }
+}
+
+impl From<ColorU8> for (u8, u8, u8, u8) {
+    #[inline(always)]
+    fn from(c: ColorU8) -> Self {
+        (c.r, c.g, c.b, c.a)
+    }
+}
+
+/// Opaque color, with the channels scaled into `[0, 1]`.
+impl From<(u8, u8, u8)> for ColorF32 {
+    #[inline(always)]
+    fn from(px: (u8, u8, u8)) -> Self {
+        ColorU8::from(px).into()
+    }
+}
+
+/// The channels are scaled into `[0, 1]`.
+impl From<(u8, u8, u8, u8)> for ColorF32 {
+    #[inline(always)]
+    fn from(px: (u8, u8, u8, u8)) -> Self {
+        ColorU8::from(px).into()
+    }
+}
+
+/// The channels are scaled into `[0, 1]`.
+impl From<ColorU8> for ColorF32 {
+    #[inline]
+    fn from(c: ColorU8) -> Self {
+        let f = |v: u8| f32::from(v) / 255.0;
+        ColorF32::new(f(c.r), f(c.g), f(c.b), f(c.a))
+    }
+}
+
+/// The channels are scaled into `[0, 255]` and rounded. Channels outside of
+/// `[0, 1]` are clamped first, so over-range colors saturate.
+impl From<ColorF32> for ColorU8 {
+    #[inline]
+    fn from(c: ColorF32) -> Self {
+        let f = |v: f32| to_channel(v.clamp(0.0, 1.0) * 255.0);
+        ColorU8::new(f(c.r), f(c.g), f(c.b), f(c.a))
+    }
+}
+
+impl Blend for ColorU8 {
+    #[inline]
+    fn blend(self, dst: Self) -> Self {
+        let src: (u8, u8, u8, u8) = self.into();
+        src.blend(dst.into()).into()
+    }
+
+    #[inline]
+    fn add(self, dst: Self) -> Self {
+        let src: (u8, u8, u8, u8) = self.into();
+        src.add(dst.into()).into()
+    }
+
+    #[inline]
+    fn multiply(self, dst: Self) -> Self {
+        let src: (u8, u8, u8, u8) = self.into();
+        src.multiply(dst.into()).into()
+    }
+}
+
+impl Blend for ColorF32 {
+    fn blend(self, dst: Self) -> Self {
+        let sa = self.a;
+        let da = dst.a * (1.0 - sa);
+        let a = sa + da;
+        if a == 0.0 {
+            return ColorF32::default();
+        }
+
+        let channel = |s: f32, d: f32| (s * sa + d * da) / a;
+        ColorF32::new(
+            channel(self.r, dst.r),
+            channel(self.g, dst.g),
+            channel(self.b, dst.b),
+            a,
+        )
+    }
+
+    #[inline]
+    fn add(self, dst: Self) -> Self {
+        ColorF32::new(
+            self.r + dst.r,
+            self.g + dst.g,
+            self.b + dst.b,
+            self.a + dst.a,
+        )
+    }
+
+    #[inline]
+    fn multiply(self, dst: Self) -> Self {
+        ColorF32::new(
+            self.r * dst.r,
+            self.g * dst.g,
+            self.b * dst.b,
+            self.a * dst.a,
+        )
+    }
+}
+
+impl<T: Float> Pixel<T> for ColorU8 {
+    #[inline]
+    fn lerp3(a: Self, b: Self, c: Self, w: [T; 3]) -> Self {
+        ColorU8::new(
+            lerp_channel(a.r, b.r, c.r, w),
+            lerp_channel(a.g, b.g, c.g, w),
+            lerp_channel(a.b, b.b, c.b, w),
+            lerp_channel(a.a, b.a, c.a, w),
+        )
+    }
+
+    #[inline]
+    fn scale(self, s: T) -> Self {
+        Pixel::scale((self.r, self.g, self.b, self.a), s).into()
+    }
+}
+
+/// Unlike `ColorU8`, the channels are neither rounded nor clamped.
+impl<T: Float> Pixel<T> for ColorF32 {
+    #[inline]
+    fn lerp3(a: Self, b: Self, c: Self, w: [T; 3]) -> Self {
+        let w = [
+            w[0].to_f32().unwrap_or(0.0),
+            w[1].to_f32().unwrap_or(0.0),
+            w[2].to_f32().unwrap_or(0.0),
+        ];
+        let f = |a: f32, b: f32, c: f32| a * w[0] + b * w[1] + c * w[2];
+        ColorF32::new(
+            f(a.r, b.r, c.r),
+            f(a.g, b.g, c.g),
+            f(a.b, b.b, c.b),
+            f(a.a, b.a, c.a),
+        )
+    }
+
+    #[inline]
+    fn scale(self, s: T) -> Self {
+        let s = s.to_f32().unwrap_or(0.0);
+        ColorF32::new(self.r * s, self.g * s, self.b * s, self.a * s)
+    }
+}
+
+/// Conversion of colors between sRGB and linear space.
+///
+/// Colors are usually given in sRGB, but interpolation and blending are
+/// only correct in linear space, so i.e. shade with `Pixel::lerp3` on
+/// linearized colors and re-encode the result.
+pub mod color {
+    /// Decode a single sRGB channel in `[0, 1]` to linear space.
+    #[inline]
+    fn decode(c: f64) -> f64 {
+        if c <= 0.04045 {
+            c / 12.92
+        } else {
+            ((c + 0.055) / 1.055).powf(2.4)
+        }
+    }
+
+    /// Encode a single linear channel to sRGB, both in `[0, 1]`.
+    #[inline]
+    fn encode(c: f64) -> f64 {
+        if c <= 0.003_130_8 {
+            c * 12.92
+        } else {
+            1.055 * c.powf(2.4_f64.recip()) - 0.055
+        }
+    }
 
-            (shapes + 1, verts, frags)
-        });
+    /// Convert an sRGB color to linear space, with channels in `[0, 1]`.
+    #[inline]
+    pub fn srgb_to_linear(px: (u8, u8, u8)) -> (f64, f64, f64) {
+        let f = |c: u8| decode(f64::from(c) / 255.0);
+        (f(px.0), f(px.1), f(px.2))
+    }
 
-        Ok(result)
+    /// Convert a linear color to sRGB. Channels outside of `[0, 1]` are
+    /// clamped first, so over-range colors saturate.
+    #[inline]
+    pub fn linear_to_srgb(px: (f64, f64, f64)) -> (u8, u8, u8) {
+        let f = |c: f64| (encode(c.clamp(0.0, 1.0)) * 255.0).round() as u8;
+        (f(px.0), f(px.1), f(px.2))
     }
 }
 
@@ -148,8 +848,11 @@ pub trait Renderer<T: Signed + AsPrimitive<usize>> {
 ///     let mesh = iter::once(triangle);
 ///
 ///     renderer.set_attr(0, (255_u8, 255_u8, 255_u8));
-///     if let Ok((s, v, f)) = renderer.draw(mesh) {
-///         println!("drawn {} primitives, {} vertices and {} fragments", s, v, f);
+///     if let Ok(stats) = renderer.draw(mesh) {
+///         println!(
+///             "drawn {} primitives, {} vertices and {} fragments",
+///             stats.shapes, stats.vertices, stats.fragments
+///         );
 ///     }
 /// }
 /// ```
@@ -157,24 +860,167 @@ pub trait Renderer<T: Signed + AsPrimitive<usize>> {
 /// # Parameters
 ///
 /// - `Px` represents the pixel type, a.k.a. a color.
-pub struct SimpleRenderer<Px: Clone> {
-    /// The currently selected color, if any.
-    color: Option<Px>,
+/// - `T` represents the primitive numeric type of the coordinates it draws,
+///   i.e. `f32` to draw `f32` meshes. See `into_f32`.
+pub struct SimpleRenderer<Px: Clone, T = f64> {
+    /// The attributes, i.e. colors, indexed by slot. The 0th is drawn by
+    /// `draw`.
+    attrs: Vec<Option<Px>>,
     /// The width of the internal buffers.
     width: usize,
     /// The height of the internal buffers.
     height: usize,
     /// The public "read-only" buffer.
     front: Vec<Px>,
-    /// The private "write-only" buffer.
+    /// The private "write-only" buffer. Empty if single-buffered.
     back: Vec<Px>,
+    /// The depth buffer, if depth testing is enabled.
+    depth: Option<Vec<f64>>,
+    /// Whether draws go directly to the front buffer.
+    single_buffered: bool,
+    /// Whether drawing an empty mesh is an error.
+    strict: bool,
+    /// The weighted color sums and total weights of accumulated splats.
+    /// Allocated lazily on the first splat.
+    splats: Vec<(f64, f64, f64, f64)>,
+    /// The blend function, unless `BlendMode::Replace` is selected.
+    blend: Option<fn(Px, Px) -> Px>,
+    /// The region drawing is restricted to, if any.
+    scissor: Option<Rectangle<usize>>,
+    /// The stencil buffer, if stencil testing is enabled.
+    stencil: Option<Vec<u8>>,
+    /// The test fragments must pass against the stencil buffer.
+    stencil_func: StencilFunc,
+    /// The primitive ids written by `draw_ids`. Allocated lazily on the
+    /// first call.
+    ids: Vec<Option<u32>>,
+    _phantom: PhantomData<T>,
 }
 
-impl<Px: Clone> SimpleRenderer<Px> {
+impl<Px: Clone, T> SimpleRenderer<Px, T> {
     /// Get a reference to the public "read-only" buffer.
     pub fn buffer(&self) -> &[Px] {
         &self.front
     }
+
+    /// Get a mutable reference to the public front buffer, i.e. for
+    /// post-processing.
+    pub fn buffer_mut(&mut self) -> &mut [Px] {
+        &mut self.front
+    }
+
+    /// Make `draw` report `RenderError::EmptyMesh` for meshes without any
+    /// shapes, i.e. to catch exhausted iterators. Off by default.
+    pub fn set_strict(&mut self, strict: bool) {
+        self.strict = strict;
+    }
+
+    /// Restrict drawing to the pixels within `scissor`, i.e. for split
+    /// screen or UI panels. Fragments outside of it are discarded before
+    /// the stencil test and aren't counted, and `None` allows drawing to the
+    /// whole buffer, which is the default.
+    ///
+    /// `clear` and `buffer_mut` aren't affected.
+    pub fn set_scissor(&mut self, scissor: Option<Rectangle<usize>>) {
+        self.scissor = scissor;
+    }
+
+    /// Report `RenderError::EmptyMesh` for the `stats` of a mesh without
+    /// any shapes, if strict.
+    #[inline(always)]
+    fn check_strict(&self, stats: Stats) -> Result<Stats, RenderError> {
+        if self.strict && stats.shapes == 0 {
+            Err(RenderError::EmptyMesh)
+        } else {
+            Ok(stats)
+        }
+    }
+
+    /// Whether `p` lies within the scissor rectangle, if any.
+    #[inline(always)]
+    fn in_scissor(&self, p: Point2<usize>) -> bool {
+        self.scissor.is_none_or(|scissor| scissor.contains(p))
+    }
+
+    /// Copy the pixels within `src_rect` of the front buffer of `src` into
+    /// the front buffer of `self`, with the top left corner at `dst`, i.e.
+    /// for compositing layers.
+    ///
+    /// `src_rect` is clipped to the bounds of `src`, and the copy is clipped
+    /// to the bounds of `self`. Like `buffer_mut`, it's not affected by the
+    /// scissor.
+    pub fn blit_from<U>(
+        &mut self,
+        src: &SimpleRenderer<Px, U>,
+        src_rect: Rectangle<usize>,
+        dst: Point2<usize>,
+    ) {
+        let bounds = Rectangle::new(0, src.width, 0, src.height);
+        let ((x0, y0), (x1, y1)) = src_rect.clamp_to(&bounds).min_max();
+        let width = (x1 - x0).min(self.width.saturating_sub(dst.0));
+        let height = (y1 - y0).min(self.height.saturating_sub(dst.1));
+
+        for row in 0..height {
+            let from = (y0 + row) * src.width + x0;
+            let to = (dst.1 + row) * self.width + dst.0;
+            self.front[to..to + width].clone_from_slice(&src.front[from..from + width]);
+        }
+    }
+
+    /// Set the stencil value of the pixel at `p`, i.e. to build a mask.
+    /// Does nothing without a stencil buffer, see `with_stencil`, or if `p`
+    /// lies outside of the buffer.
+    pub fn set_stencil(&mut self, p: Point2<usize>, val: u8) {
+        let width = self.width;
+        if p.0 >= width || p.1 >= self.height {
+            return;
+        }
+        if let Some(ref mut stencil) = self.stencil {
+            stencil[p.1 * width + p.0] = val;
+        }
+    }
+
+    /// Select the test fragments must pass against the stencil buffer to be
+    /// drawn. Defaults to `StencilFunc::Always`.
+    pub fn set_stencil_func(&mut self, func: StencilFunc) {
+        self.stencil_func = func;
+    }
+
+    /// Draw meshes with `f32` coordinates instead, keeping the buffers and
+    /// all settings.
+    pub fn into_f32(self) -> SimpleRenderer<Px, f32> {
+        self.cast()
+    }
+
+    /// Change the coordinate type, keeping everything else.
+    fn cast<U>(self) -> SimpleRenderer<Px, U> {
+        SimpleRenderer {
+            attrs: self.attrs,
+            width: self.width,
+            height: self.height,
+            front: self.front,
+            back: self.back,
+            depth: self.depth,
+            single_buffered: self.single_buffered,
+            strict: self.strict,
+            splats: self.splats,
+            blend: self.blend,
+            scissor: self.scissor,
+            stencil: self.stencil,
+            stencil_func: self.stencil_func,
+            ids: self.ids,
+            _phantom: PhantomData,
+        }
+    }
+
+    /// Get a mutable reference to the buffer draws are written to.
+    fn target(&mut self) -> &mut [Px] {
+        if self.single_buffered {
+            &mut self.front
+        } else {
+            &mut self.back
+        }
+    }
 }
 
 impl<Px: Default + Clone> SimpleRenderer<Px> {
@@ -182,42 +1028,718 @@ impl<Px: Default + Clone> SimpleRenderer<Px> {
     /// allocated on the heap.
     pub fn new(width: usize, height: usize) -> Self {
         SimpleRenderer {
-            color: None,
+            attrs: Vec::new(),
             width,
             height,
             front: vec![Px::default(); width * height],
             back: vec![Px::default(); width * height],
+            depth: None,
+            single_buffered: false,
+            strict: false,
+            splats: Vec::new(),
+            blend: None,
+            scissor: None,
+            stencil: None,
+            stencil_func: StencilFunc::Always,
+            ids: Vec::new(),
+            _phantom: PhantomData,
         }
     }
-}
 
-impl<Px: Clone> Renderer<f64> for SimpleRenderer<Px> {
-    type Pixel = Px;
-    type Attr = Self::Pixel;
-    type Error = ();
-
-    fn put_pixel(&mut self, p: Point2<usize>, px: Self::Pixel) {
-        self.back[p.1 * self.width + p.0] = px;
+    /// Create a new `SimpleRenderer` with the specified size and a depth
+    /// buffer. Fragments with a depth are only drawn if they are nearer than
+    /// whatever was previously drawn at the same pixel.
+    pub fn with_depth(width: usize, height: usize) -> Self {
+        SimpleRenderer {
+            depth: Some(vec![f64::INFINITY; width * height]),
+            ..Self::new(width, height)
+        }
     }
 
-    fn swap(&mut self) {
-        mem::swap(&mut self.front, &mut self.back)
+    /// Create a new `SimpleRenderer` with the specified size and a stencil
+    /// buffer, which is initially zero everywhere. Fragments are only drawn
+    /// where the stencil values pass the test selected by
+    /// `set_stencil_func`.
+    pub fn with_stencil(width: usize, height: usize) -> Self {
+        SimpleRenderer {
+            stencil: Some(vec![0; width * height]),
+            ..Self::new(width, height)
+        }
     }
 
-    fn width(&self) -> usize {
-        self.width
+    /// Create a new single-buffered `SimpleRenderer` with the specified size.
+    /// Only the front buffer is allocated.
+    ///
+    /// Draws are written directly to the front buffer and are immediately
+    /// visible through `buffer`, without calling `swap`, which is a no-op.
+    /// This lowers latency and memory usage, at the cost of partially drawn
+    /// frames being observable, a.k.a. tearing.
+    pub fn single_buffered(width: usize, height: usize) -> Self {
+        SimpleRenderer {
+            attrs: Vec::new(),
+            width,
+            height,
+            front: vec![Px::default(); width * height],
+            back: Vec::new(),
+            depth: None,
+            single_buffered: true,
+            strict: false,
+            splats: Vec::new(),
+            blend: None,
+            scissor: None,
+            stencil: None,
+            stencil_func: StencilFunc::Always,
+            ids: Vec::new(),
+            _phantom: PhantomData,
+        }
     }
+}
 
-    fn height(&self) -> usize {
-        self.height
+impl<Px: Default + Clone, T> SimpleRenderer<Px, T> {
+    /// Change the size of the buffers, i.e. for resizable windows. The
+    /// attributes and all other settings are kept.
+    ///
+    /// The overlapping top left region of every buffer is kept, so shrinking
+    /// truncates the buffers, and the area gained by growing is filled with
+    /// `Px::default()`. Depth is reset to infinity and the stencil to zero
+    /// there.
+    pub fn resize(&mut self, width: usize, height: usize) {
+        let old = self.width;
+        self.front = resized(&self.front, old, width, height, Px::default());
+        if !self.single_buffered {
+            self.back = resized(&self.back, old, width, height, Px::default());
+        }
+        if let Some(ref mut depth) = self.depth {
+            *depth = resized(depth, old, width, height, f64::INFINITY);
+        }
+        if let Some(ref mut stencil) = self.stencil {
+            *stencil = resized(stencil, old, width, height, 0);
+        }
+        if !self.splats.is_empty() {
+            self.splats = resized(&self.splats, old, width, height, (0.0, 0.0, 0.0, 0.0));
+        }
+        if !self.ids.is_empty() {
+            self.ids = resized(&self.ids, old, width, height, None);
+        }
+
+        self.width = width;
+        self.height = height;
+    }
+}
+
+/// Copy the overlapping top left region of `buffer`, `width` wide, into a
+/// new buffer of `new_width` by `new_height`, filling the rest with `fill`.
+fn resized<T: Clone>(
+    buffer: &[T],
+    width: usize,
+    new_width: usize,
+    new_height: usize,
+    fill: T,
+) -> Vec<T> {
+    let mut new = vec![fill; new_width * new_height];
+    let cols = width.min(new_width);
+    for (src, dst) in buffer.chunks(width.max(1)).zip(new.chunks_mut(new_width.max(1))) {
+        dst[..cols].clone_from_slice(&src[..cols]);
+    }
+
+    new
+}
+
+impl<Px: Blend + Clone, T> SimpleRenderer<Px, T> {
+    /// Select how drawn pixels are combined with the buffer. Defaults to
+    /// `BlendMode::Replace`.
+    pub fn set_blend(&mut self, mode: BlendMode) {
+        self.blend = blend_fn(mode);
+    }
+}
+
+/// Get the blend function of `mode`, or `None` for `BlendMode::Replace`.
+fn blend_fn<Px: Blend>(mode: BlendMode) -> Option<fn(Px, Px) -> Px> {
+    match mode {
+        BlendMode::Replace => None,
+        BlendMode::Over => Some(Px::blend),
+        BlendMode::Add => Some(Px::add),
+        BlendMode::Multiply => Some(Px::multiply),
+    }
+}
+
+/// A builder configuring a `SimpleRenderer` in one go, instead of picking a
+/// constructor and calling the many setters.
+///
+/// The defaults match `SimpleRenderer::new`.
+///
+/// # Example
+///
+/// ```rust
+/// use qr::{BlendMode, Rectangle, SimpleRendererBuilder};
+///
+/// let renderer = SimpleRendererBuilder::<(u8, u8, u8, u8)>::new()
+///     .depth(true)
+///     .blend(BlendMode::Over)
+///     .scissor(Some(Rectangle::new(0, 64, 0, 128)))
+///     .build(128, 128);
+/// ```
+#[derive(Clone, Copy, Debug)]
+pub struct SimpleRendererBuilder<Px> {
+    msaa: usize,
+    single_buffered: bool,
+    depth: bool,
+    stencil: bool,
+    stencil_func: StencilFunc,
+    blend: Option<fn(Px, Px) -> Px>,
+    scissor: Option<Rectangle<usize>>,
+    strict: bool,
+}
+
+impl<Px> SimpleRendererBuilder<Px> {
+    /// Create a builder with the default settings.
+    pub fn new() -> Self {
+        SimpleRendererBuilder {
+            msaa: 1,
+            single_buffered: false,
+            depth: false,
+            stencil: false,
+            stencil_func: StencilFunc::Always,
+            blend: None,
+            scissor: None,
+            strict: false,
+        }
+    }
+
+    /// Supersample every pixel `factor` by `factor` times, i.e. allocate
+    /// `factor` times as wide and high buffers to `downsample(factor)`
+    /// into the anti-aliased image after drawing. The mesh has to be scaled
+    /// by `factor` too, and so has the scissor.
+    ///
+    /// # Panics
+    ///
+    /// If `factor` is zero.
+    pub fn msaa(mut self, factor: usize) -> Self {
+        assert!(factor > 0, "supersampling factor is zero");
+        self.msaa = factor;
+        self
+    }
+
+    /// Draw directly to the front buffer, like
+    /// `SimpleRenderer::single_buffered`.
+    pub fn single_buffered(mut self, single_buffered: bool) -> Self {
+        self.single_buffered = single_buffered;
+        self
+    }
+
+    /// Allocate a depth buffer, like `SimpleRenderer::with_depth`.
+    pub fn depth(mut self, depth: bool) -> Self {
+        self.depth = depth;
+        self
+    }
+
+    /// Allocate a stencil buffer, like `SimpleRenderer::with_stencil`.
+    pub fn stencil(mut self, stencil: bool) -> Self {
+        self.stencil = stencil;
+        self
+    }
+
+    /// See `SimpleRenderer::set_stencil_func`.
+    pub fn stencil_func(mut self, func: StencilFunc) -> Self {
+        self.stencil_func = func;
+        self
+    }
+
+    /// See `SimpleRenderer::set_scissor`.
+    pub fn scissor(mut self, scissor: Option<Rectangle<usize>>) -> Self {
+        self.scissor = scissor;
+        self
+    }
+
+    /// See `SimpleRenderer::set_strict`.
+    pub fn strict(mut self, strict: bool) -> Self {
+        self.strict = strict;
+        self
+    }
+}
+
+impl<Px: Blend> SimpleRendererBuilder<Px> {
+    /// See `SimpleRenderer::set_blend`.
+    pub fn blend(mut self, mode: BlendMode) -> Self {
+        self.blend = blend_fn(mode);
+        self
+    }
+}
+
+impl<Px: Default + Clone> SimpleRendererBuilder<Px> {
+    /// Create the configured `SimpleRenderer`, which is `width` by `height`
+    /// pixels before supersampling.
+    pub fn build(self, width: usize, height: usize) -> SimpleRenderer<Px> {
+        let (width, height) = (width * self.msaa, height * self.msaa);
+        let renderer = if self.single_buffered {
+            SimpleRenderer::single_buffered(width, height)
+        } else {
+            SimpleRenderer::new(width, height)
+        };
+
+        SimpleRenderer {
+            depth: if self.depth {
+                Some(vec![f64::INFINITY; width * height])
+            } else {
+                None
+            },
+            stencil: if self.stencil {
+                Some(vec![0; width * height])
+            } else {
+                None
+            },
+            stencil_func: self.stencil_func,
+            blend: self.blend,
+            scissor: self.scissor,
+            strict: self.strict,
+            ..renderer
+        }
+    }
+}
+
+impl<Px> Default for SimpleRendererBuilder<Px> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<Px: PartialEq + Clone, T> SimpleRenderer<Px, T> {
+    /// Replace the 4-connected region of pixels equal to the one at `seed`
+    /// with `fill`, a.k.a. a paint bucket, in the back buffer, or the front
+    /// buffer if single-buffered.
+    ///
+    /// Like `clear`, it's not affected by the scissor. A `seed` outside of
+    /// the buffer does nothing.
+    pub fn flood_fill(&mut self, seed: Point2<usize>, fill: Px) {
+        let (width, height) = (self.width, self.height);
+        if seed.0 >= width || seed.1 >= height {
+            return;
+        }
+
+        let buffer = self.target();
+        let target = buffer[seed.1 * width + seed.0].clone();
+        if target == fill {
+            return;
+        }
+
+        // fill a whole span of the row at once, then push the start of every
+        // span touching it in the rows above and below
+        let mut stack = vec![seed];
+        while let Some((x, y)) = stack.pop() {
+            let row = y * width;
+            if buffer[row + x] != target {
+                continue;
+            }
+
+            let mut x0 = x;
+            while x0 > 0 && buffer[row + x0 - 1] == target {
+                x0 -= 1;
+            }
+            let mut x1 = x + 1;
+            while x1 < width && buffer[row + x1] == target {
+                x1 += 1;
+            }
+            for px in &mut buffer[row + x0..row + x1] {
+                *px = fill.clone();
+            }
+
+            // the row above the first one wraps around and is skipped
+            for &y in &[y.wrapping_sub(1), y + 1] {
+                if y >= height {
+                    continue;
+                }
+                let mut inside = false;
+                for x in x0..x1 {
+                    let matches = buffer[y * width + x] == target;
+                    if matches && !inside {
+                        stack.push((x, y));
+                    }
+                    inside = matches;
+                }
+            }
+        }
     }
+}
+
+#[cfg(feature = "rayon")]
+impl<Px: Clone + Send + Sync> SimpleRenderer<Px> {
+    /// Draw the `mesh` like `draw`, using all threads of the rayon pool.
+    ///
+    /// The shapes are rasterized in parallel first, then the buffer is split
+    /// into horizontal bands, which are written in parallel. Every band
+    /// applies the fragments in mesh order, so depth testing and blending
+    /// give the same result as `draw`.
+    pub fn draw_parallel<C, D, I>(&mut self, mesh: I) -> Result<Stats, RenderError>
+    where
+        C: Coord<f64>,
+        D: Drawable<f64, C> + Send,
+        I: Iterator<Item = D>,
+    {
+        use rayon::prelude::*;
+
+        let mesh = mesh.collect::<Vec<_>>();
+        let shapes = mesh.len();
+        let vertices = mesh.iter().map(Drawable::vertices).sum();
+        if self.strict && shapes == 0 {
+            return Err(RenderError::EmptyMesh);
+        }
+
+        let (width, height) = (self.width, self.height);
+        if width == 0 || height == 0 {
+            return Ok(Stats {
+                shapes,
+                vertices,
+                fragments: 0,
+            });
+        }
+        let px = self.attrs.first().and_then(Clone::clone);
+        let rows = height.div_ceil(rayon::current_num_threads());
+        let bands = height.div_ceil(rows);
 
-    fn get_attr(&self, _attr: usize) -> Option<Self::Attr> {
-        self.color.clone()
+        // per shape, the in-bounds fragments of every band
+        let scissor = self.scissor;
+        let (stencil, stencil_func) = (self.stencil.as_ref(), self.stencil_func);
+        let fragments = mesh
+            .into_par_iter()
+            .map(|drawable| {
+                let mut shape = vec![Vec::new(); bands];
+                for c in drawable {
+                    let (x, y) = c.point();
+                    if x >= 0.0 && (x as usize) < width && y >= 0.0 && (y as usize) < height {
+                        let (x, y) = (x as usize, y as usize);
+                        let masked = stencil.is_some_and(|s| !stencil_func.test(s[y * width + x]));
+                        if !masked && scissor.is_none_or(|scissor| scissor.contains((x, y))) {
+                            shape[y / rows].push(((x, y % rows), c.depth()));
+                        }
+                    }
+                }
+                shape
+            })
+            .collect::<Vec<_>>();
+
+        let blend = self.blend;
+        let mut depth = match self.depth {
+            Some(ref mut depth) => depth.chunks_mut(rows * width).map(Some).collect(),
+            None => (0..bands).map(|_| None).collect::<Vec<_>>(),
+        };
+        let target = if self.single_buffered {
+            &mut self.front
+        } else {
+            &mut self.back
+        };
+        let fragments = target
+            .par_chunks_mut(rows * width)
+            .zip(depth.par_iter_mut())
+            .enumerate()
+            .map(|(band, (pixels, depth))| {
+                let mut frags = 0;
+                for &((x, y), z) in fragments.iter().flat_map(|shape| &shape[band]) {
+                    let i = y * width + x;
+                    if let (Some(z), Some(depth)) = (z, depth.as_mut()) {
+                        if z < depth[i] {
+                            depth[i] = z;
+                        } else {
+                            continue;
+                        }
+                    }
+                    if let Some(ref px) = px {
+                        pixels[i] = match blend {
+                            Some(blend) => blend(px.clone(), pixels[i].clone()),
+                            None => px.clone(),
+                        };
+                    }
+                    frags += 1;
+                }
+                frags
+            })
+            .sum();
+
+        Ok(Stats {
+            shapes,
+            vertices,
+            fragments,
+        })
     }
+}
+
+impl<T> SimpleRenderer<(u8, u8, u8), T> {
+    /// Add a splat of `color` with `weight` to the pixel containing `p`.
+    /// Splats outside of the buffer are ignored.
+    ///
+    /// Nothing is drawn until `resolve_splats` is called.
+    pub fn splat_accumulate(&mut self, p: Point2<f64>, color: (u8, u8, u8), weight: f64) {
+        let (x, y) = p;
+        if x < 0.0 || y < 0.0 || x as usize >= self.width || y as usize >= self.height {
+            return;
+        }
 
-    fn set_attr(&mut self, _attr: usize, color: Self::Attr) {
-        self.color = Some(color);
+        if self.splats.is_empty() {
+            self.splats = vec![(0.0, 0.0, 0.0, 0.0); self.width * self.height];
+        }
+
+        let acc = &mut self.splats[y as usize * self.width + x as usize];
+        acc.0 += f64::from(color.0) * weight;
+        acc.1 += f64::from(color.1) * weight;
+        acc.2 += f64::from(color.2) * weight;
+        acc.3 += weight;
+    }
+
+    /// Normalize the accumulated splats by their total weight and write
+    /// them to the back buffer, or the front buffer if single-buffered.
+    /// Pixels without any splats are left untouched. The accumulation buffer
+    /// is reset afterwards.
+    pub fn resolve_splats(&mut self) {
+        let splats = mem::take(&mut self.splats);
+        for (px, (r, g, b, w)) in self.target().iter_mut().zip(splats) {
+            if w > 0.0 {
+                let w_recip = w.recip();
+                *px = (
+                    (r * w_recip).round() as u8,
+                    (g * w_recip).round() as u8,
+                    (b * w_recip).round() as u8,
+                );
+            }
+        }
+    }
+
+    /// Box-filter every `factor` by `factor` block of the front buffer into
+    /// a pixel of a new renderer, i.e. to anti-alias a supersampled image.
+    /// The result is in the front buffer of the new renderer.
+    ///
+    /// The pixels are averaged in linear space, see `color`, so i.e. a
+    /// black and white checkerboard turns into a gray of the same
+    /// brightness, rather than a darker one.
+    ///
+    /// The new renderer is `width / factor` by `height / factor`, so
+    /// trailing rows and columns, which don't fill a whole block, are
+    /// truncated.
+    ///
+    /// # Panics
+    ///
+    /// If `factor` is zero.
+    pub fn downsample(&self, factor: usize) -> SimpleRenderer<(u8, u8, u8), T> {
+        assert!(factor > 0, "downsampling factor is zero");
+
+        let (width, height) = (self.width / factor, self.height / factor);
+        let mut renderer = SimpleRenderer::new(width, height).cast();
+        let n_recip = ((factor * factor) as f64).recip();
+
+        for y in 0..height {
+            for x in 0..width {
+                let mut sum = (0.0, 0.0, 0.0);
+                for row in y * factor..(y + 1) * factor {
+                    let start = row * self.width + x * factor;
+                    for &px in &self.front[start..start + factor] {
+                        let (r, g, b) = color::srgb_to_linear(px);
+                        sum = (sum.0 + r, sum.1 + g, sum.2 + b);
+                    }
+                }
+
+                let avg = (sum.0 * n_recip, sum.1 * n_recip, sum.2 * n_recip);
+                renderer.front[y * width + x] = color::linear_to_srgb(avg);
+            }
+        }
+
+        renderer
+    }
+}
+
+impl<T> SimpleRenderer<(f32, f32, f32), T> {
+    /// Tone-map the linear, high dynamic range front buffer into a
+    /// displayable sRGB image in the front buffer of a new renderer, i.e.
+    /// after accumulating light with `BlendMode::Add`.
+    ///
+    /// Every channel is scaled by `exposure` and compressed into `[0, 1)`
+    /// with the Reinhard operator `c / (1 + c)`, so bright pixels approach
+    /// white without clipping. Negative channels map to 0.
+    pub fn tone_map(&self, exposure: f32) -> SimpleRenderer<(u8, u8, u8), T> {
+        let mut renderer = SimpleRenderer::new(self.width, self.height).cast();
+        let reinhard = |c: f32| {
+            let c = f64::from((c * exposure).max(0.0));
+            c / (1.0 + c)
+        };
+
+        for (dst, &(r, g, b)) in renderer.front.iter_mut().zip(&self.front) {
+            *dst = color::linear_to_srgb((reinhard(r), reinhard(g), reinhard(b)));
+        }
+
+        renderer
+    }
+}
+
+impl<Px: Clone, T: Float + Signed + AsPrimitive<usize>> SimpleRenderer<Px, T> {
+    /// Draw the `mesh` into the id buffer instead of the pixel buffers, i.e.
+    /// to pick objects with `id_at`. The fragments of the `i`th shape of
+    /// the mesh are written as `id_of(i)`.
+    ///
+    /// Fragments go through the scissor, stencil and depth tests like in
+    /// `draw`, so with a depth buffer every pixel ends up with the id of
+    /// the nearest shape. The depth buffer is shared with `draw`, so
+    /// `clear` it first when picking a scene which was already drawn.
+    pub fn draw_ids<C, D, I, F>(&mut self, mesh: I, id_of: F) -> Result<Stats, RenderError>
+    where
+        C: Coord<T>,
+        D: Drawable<T, C>,
+        I: Iterator<Item = D>,
+        F: Fn(usize) -> u32,
+    {
+        let (width, height) = (self.width, self.height);
+        if self.ids.is_empty() {
+            self.ids = vec![None; width * height];
+        }
+
+        let mut stats = Stats::default();
+        for (i, drawable) in mesh.enumerate() {
+            let id = id_of(i);
+            stats.shapes += 1;
+            stats.vertices += drawable.vertices();
+            for (p, c) in visible(drawable, width, height) {
+                if !self.stencil_test(p) {
+                    continue;
+                }
+                if let Some(z) = c.depth() {
+                    if !self.depth_test(p, z) {
+                        continue;
+                    }
+                }
+                self.ids[p.1 * width + p.0] = Some(id);
+                stats.fragments += 1;
+            }
+        }
+
+        self.check_strict(stats)
+    }
+
+    /// Get the id `draw_ids` wrote at `p`, if any. Nothing is written until
+    /// `draw_ids` is called, and `clear` erases all ids.
+    pub fn id_at(&self, p: Point2<usize>) -> Option<u32> {
+        if p.0 >= self.width || p.1 >= self.height {
+            return None;
+        }
+        self.ids.get(p.1 * self.width + p.0).and_then(|&id| id)
+    }
+}
+
+impl<Px: Clone, T: Float + Signed + AsPrimitive<usize>> Renderer<T> for SimpleRenderer<Px, T> {
+    type Pixel = Px;
+    type Attr = Self::Pixel;
+    type Error = RenderError;
+
+    /// Put `px` at `p`, unless `p` lies outside of the scissor rectangle.
+    fn put_pixel(&mut self, p: Point2<usize>, px: Self::Pixel) {
+        if !self.in_scissor(p) {
+            return;
+        }
+
+        let index = p.1 * self.width + p.0;
+        let blend = self.blend;
+        let dst = &mut self.target()[index];
+        *dst = match blend {
+            Some(blend) => blend(px, dst.clone()),
+            None => px,
+        };
+    }
+
+    /// Get the pixel at `p` of the back buffer, or the front buffer if
+    /// single-buffered.
+    fn get_pixel(&self, p: Point2<usize>) -> Option<Self::Pixel> {
+        if p.0 >= self.width || p.1 >= self.height {
+            return None;
+        }
+
+        let buffer = if self.single_buffered {
+            &self.front
+        } else {
+            &self.back
+        };
+        Some(buffer[p.1 * self.width + p.0].clone())
+    }
+
+    /// Depth is stored as `f64` regardless of `T`.
+    fn depth_test(&mut self, p: Point2<usize>, z: T) -> bool {
+        let index = p.1 * self.width + p.0;
+        let z = z.to_f64().unwrap_or(f64::NAN);
+        match self.depth {
+            Some(ref mut depth) if z < depth[index] => {
+                depth[index] = z;
+                true
+            }
+            Some(_) => false,
+            None => true,
+        }
+    }
+
+    /// The scissor test is done first, so scissored fragments are discarded
+    /// before they're counted or update the depth buffer.
+    fn stencil_test(&self, p: Point2<usize>) -> bool {
+        if !self.in_scissor(p) {
+            return false;
+        }
+
+        match self.stencil {
+            Some(ref stencil) => self.stencil_func.test(stencil[p.1 * self.width + p.0]),
+            None => true,
+        }
+    }
+
+    /// Fill the back buffer, or the front buffer if single-buffered, with
+    /// `px`. The depth and id buffers are reset too, if any, but not the
+    /// stencil buffer.
+    fn clear(&mut self, px: Self::Pixel) {
+        for p in self.target().iter_mut() {
+            *p = px.clone();
+        }
+        if let Some(ref mut depth) = self.depth {
+            for z in depth.iter_mut() {
+                *z = f64::INFINITY;
+            }
+        }
+        self.ids = Vec::new();
+    }
+
+    fn swap(&mut self) {
+        if !self.single_buffered {
+            mem::swap(&mut self.front, &mut self.back)
+        }
+    }
+
+    fn width(&self) -> usize {
+        self.width
+    }
+
+    fn height(&self) -> usize {
+        self.height
+    }
+
+    fn get_attr(&self, attr: usize) -> Option<Self::Attr> {
+        self.attrs.get(attr).and_then(Clone::clone)
+    }
+
+    fn set_attr(&mut self, attr: usize, color: Self::Attr) {
+        if attr >= self.attrs.len() {
+            self.attrs.resize(attr + 1, None);
+        }
+        self.attrs[attr] = Some(color);
+    }
+
+    fn draw<C: Coord<T>, D: Drawable<T, C>, I: Iterator<Item = D>>(
+        &mut self,
+        mesh: I,
+    ) -> Result<Stats, Self::Error> {
+        let px = self.get_attr(0).map(|attr| self.clamp_attr(attr));
+        let stats = rasterize(self, mesh, |_, _| Some(px.clone()));
+        self.check_strict(stats)
+    }
+
+    fn draw_with<C, D, I, F>(&mut self, mesh: I, mut shade: F) -> Result<Stats, Self::Error>
+    where
+        C: Coord<T>,
+        D: Drawable<T, C>,
+        I: Iterator<Item = D>,
+        F: FnMut(&C) -> Option<Self::Pixel>,
+    {
+        let stats = rasterize(self, mesh, |_, c| shade(c).map(Some));
+        self.check_strict(stats)
     }
 }
 
@@ -238,10 +1760,960 @@ mod tests {
 
         let triangle = Triangle::with_points([v1, v2, v3]);
 
+        renderer.set_attr(0, 1);
+        let stats = renderer.draw(iter::once(triangle)).unwrap();
+        assert_eq!(stats.shapes, 1);
+        assert_eq!(stats.vertices, 3);
+        assert_eq!(stats.fragments, 16 * 16);
+
+        renderer.swap();
+        assert_eq!(renderer.buffer(), [1_u8; 16 * 16].as_ref());
+    }
+
+    #[test]
+    fn simple_f32() {
+        use std::iter;
+        use line::Line;
+        use tri::Triangle;
+
+        let mut renderer = SimpleRenderer::<u8>::new(16, 16).into_f32();
+
+        let v1 = (0.0_f32, 0.0_f32);
+        let v2 = (100.0, 0.0);
+        let v3 = (0.0, 100.0);
+
+        let triangle = Triangle::with_points([v1, v2, v3]);
+
+        renderer.set_attr(0, 1);
+        let stats = renderer.draw(iter::once(triangle)).unwrap();
+        assert_eq!(stats.shapes, 1);
+        assert_eq!(stats.vertices, 3);
+        assert_eq!(stats.fragments, 16 * 16);
+
+        renderer.swap();
+        assert_eq!(renderer.buffer(), [1_u8; 16 * 16].as_ref());
+
+        renderer.set_attr(0, 2);
+        let line = Line::new((0.0_f32, 3.0), (15.0, 3.0));
+        assert_eq!(renderer.draw(iter::once(line)).unwrap().fragments, 16);
+        let rect = Rectangle::new(2.0_f32, 4.0, 8.0, 10.0);
+        assert_eq!(renderer.draw(iter::once(rect)).unwrap().fragments, 4);
+        renderer.swap();
+        assert_eq!(renderer.buffer().iter().filter(|&&px| px == 2).count(), 20);
+    }
+
+    #[test]
+    fn draw_with() {
+        use std::iter;
+        use tri::Triangle;
+
+        let mut renderer = SimpleRenderer::<u8>::new(16, 16);
+        let triangle = Triangle::with_points([(0.0, 0.0), (15.0, 0.0), (0.0, 15.0)]);
+
+        let stats = renderer
+            .draw_with(iter::once(triangle), |&(_, b)| {
+                if b[0] >= 0.5 {
+                    Some((b[0] * 255.0).round() as u8)
+                } else {
+                    None
+                }
+            })
+            .unwrap();
+        renderer.swap();
+
+        let buffer = renderer.buffer();
+        assert_eq!(buffer[0], 255);
+        assert_eq!(buffer[4 * 16 + 3], 136);
+        // discarded fragments are neither drawn nor counted
+        assert_eq!(buffer[15], 0);
+        assert_eq!(buffer[8 * 16 + 7], 0);
+        assert_eq!(stats.fragments, buffer.iter().filter(|&&px| px != 0).count());
+    }
+
+    #[test]
+    fn blend() {
+        use std::iter;
+        use rect::Rectangle;
+
+        let mut renderer = SimpleRenderer::new(4, 4);
+        let rect = Rectangle::new(0.0, 4.0, 0.0, 4.0);
+
+        renderer.set_attr(0, (0, 0, 255, 255));
+        assert!(renderer.draw(iter::once(rect)).is_ok());
+
+        renderer.set_blend(BlendMode::Over);
+        renderer.set_attr(0, (255, 0, 0, 128));
+        assert!(renderer.draw(iter::once(rect)).is_ok());
+
+        renderer.swap();
+        assert_eq!(renderer.buffer()[0], (128, 0, 127, 255));
+    }
+
+    #[test]
+    fn blend_modes() {
+        use std::iter;
+        use rect::Rectangle;
+
+        let gray = (128, 128, 128, 255);
+        let (white, black) = ((255, 255, 255, 255), (0, 0, 0, 255));
+        assert_eq!(gray.add(gray), white);
+        assert_eq!(gray.multiply(white), gray);
+        assert_eq!(white.multiply(gray), gray);
+        assert_eq!(gray.multiply(black), black);
+
+        let gray = ColorU8::from(gray);
+        assert_eq!(gray.add(gray), white.into());
+        assert_eq!(gray.multiply(white.into()), gray);
+        let gray = ColorF32::new(0.5, 0.5, 0.5, 1.0);
+        assert_eq!(gray.multiply(ColorF32::new(1.0, 1.0, 1.0, 1.0)), gray);
+        assert_eq!(gray.add(gray), ColorF32::new(1.0, 1.0, 1.0, 2.0));
+
+        let mut renderer = SimpleRenderer::single_buffered(2, 1);
+        renderer.clear(white);
+        renderer.set_blend(BlendMode::Multiply);
+        renderer.set_attr(0, (128, 128, 128, 255));
+        assert!(renderer
+            .draw(iter::once(Rectangle::new(0.0, 1.0, 0.0, 1.0)))
+            .is_ok());
+        assert_eq!(renderer.buffer(), [(128, 128, 128, 255), white].as_ref());
+    }
+
+    #[test]
+    fn lerp3() {
+        use std::iter;
+        use tri::Triangle;
+
+        let (a, b, c) = ((255, 0, 0), (0, 255, 0), (0, 0, 255));
+        assert_eq!(Pixel::lerp3(a, b, c, [1.0, 0.0, 0.0]), a);
+        assert_eq!(Pixel::lerp3(a, b, c, [0.0, 0.0, 1.0]), c);
+        let third = 1.0 / 3.0;
+        assert_eq!(Pixel::lerp3(a, b, c, [third; 3]), (85, 85, 85));
+
+        let (a, b, c) = ((30, 60, 90, 255), (60, 90, 120, 0), (90, 120, 150, 126));
+        assert_eq!(Pixel::lerp3(a, b, c, [third; 3]), (60, 90, 120, 127));
+        assert_eq!(Pixel::scale((200, 100, 50), 0.5), (100, 50, 25));
+        assert_eq!(Pixel::scale((200, 100, 50), 2.0), (255, 200, 100));
+
+        let mut renderer = SimpleRenderer::new(8, 8);
+        let triangle = Triangle::with_points([(0.0, 0.0), (3.0, 0.0), (0.0, 3.0)]);
+        let (a, b, c) = ((255, 0, 0), (0, 255, 0), (0, 0, 255));
+        let shade = |&(_, w): &((f64, f64), [f64; 3])| Some(Pixel::lerp3(a, b, c, w));
+        assert!(renderer.draw_with(iter::once(triangle), shade).is_ok());
+        renderer.swap();
+        assert_eq!(renderer.buffer()[0], a);
+        assert_eq!(renderer.buffer()[8 + 1], (85, 85, 85));
+    }
+
+    #[test]
+    fn srgb() {
+        use super::color::{linear_to_srgb, srgb_to_linear};
+
+        assert_eq!(srgb_to_linear((0, 0, 0)), (0.0, 0.0, 0.0));
+        assert_eq!(srgb_to_linear((255, 255, 255)), (1.0, 1.0, 1.0));
+        for &px in &[(255, 255, 255), (128, 128, 128), (0, 0, 0), (10, 70, 200)] {
+            assert_eq!(linear_to_srgb(srgb_to_linear(px)), px);
+        }
+
+        let (gray, _, _) = srgb_to_linear((128, 128, 128));
+        assert!((gray - 0.2158605).abs() < 1e-6);
+
+        assert_eq!(linear_to_srgb((-0.5, 1.5, f64::NAN)), (0, 255, 0));
+    }
+
+    #[test]
+    fn colors() {
+        assert_eq!(ColorU8::from((1, 2, 3)), ColorU8::new(1, 2, 3, 255));
+        assert_eq!(ColorU8::from((1, 2, 3, 4)), ColorU8::new(1, 2, 3, 4));
+        assert_eq!(
+            <(u8, u8, u8, u8)>::from(ColorU8::new(1, 2, 3, 4)),
+            (1, 2, 3, 4)
+        );
+        assert_eq!(
+            ColorF32::from((0, 255, 0)),
+            ColorF32::new(0.0, 1.0, 0.0, 1.0)
+        );
+        assert_eq!(
+            ColorF32::from((255, 0, 0, 0)),
+            ColorF32::new(1.0, 0.0, 0.0, 0.0)
+        );
+
+        for &px in &[
+            (0, 0, 0, 0),
+            (255, 255, 255, 255),
+            (128, 64, 32, 16),
+            (1, 127, 254, 200),
+        ] {
+            let c = ColorU8::from(px);
+            assert_eq!(ColorU8::from(ColorF32::from(c)), c);
+        }
+        assert_eq!(
+            ColorU8::from(ColorF32::new(-0.5, 1.5, 0.5, 1.0)),
+            ColorU8::new(0, 255, 128, 255)
+        );
+
+        let over = ColorF32::new(1.0, 0.0, 0.0, 0.5).blend(ColorF32::new(0.0, 0.0, 1.0, 1.0));
+        assert_eq!(over, ColorF32::new(0.5, 0.0, 0.5, 1.0));
+        let over = ColorU8::new(255, 0, 0, 128).blend(ColorU8::new(0, 0, 255, 255));
+        assert_eq!(over, (255_u8, 0, 0, 128).blend((0, 0, 255, 255)).into());
+
+        let w = [0.5, 0.25, 0.25];
+        let (a, b, c) = (
+            ColorU8::new(200, 0, 0, 255),
+            ColorU8::new(0, 200, 0, 255),
+            ColorU8::new(0, 0, 200, 255),
+        );
+        assert_eq!(Pixel::lerp3(a, b, c, w), ColorU8::new(100, 50, 50, 255));
+        let lerp = Pixel::lerp3(ColorF32::from(a), b.into(), c.into(), w);
+        assert_eq!(ColorU8::from(lerp), ColorU8::new(100, 50, 50, 255));
+    }
+
+    #[cfg(feature = "rayon")]
+    #[test]
+    fn draw_parallel() {
+        use tri::Triangle;
+
+        let scene = vec![
+            Triangle::with_points([(0.0, 0.0), (60.0, 3.0), (5.0, 47.0)]),
+            Triangle::with_points([(10.0, 40.0), (63.0, 10.0), (50.0, 63.0)]),
+            Triangle::with_points([(-8.0, 20.0), (40.0, 70.0), (30.0, 12.0)]),
+            Triangle::with_points([(20.0, 20.0), (44.0, 20.0), (32.0, 44.0)]),
+        ];
+
+        let mut serial = SimpleRenderer::new(64, 64);
+        let mut parallel = SimpleRenderer::new(64, 64);
+        for renderer in &mut [&mut serial, &mut parallel] {
+            renderer.set_blend(BlendMode::Over);
+            renderer.set_attr(0, (255, 64, 0, 100));
+        }
+
+        let expected = serial.draw(scene.clone().into_iter()).unwrap();
+        let stats = parallel.draw_parallel(scene.clone().into_iter()).unwrap();
+        serial.swap();
+        parallel.swap();
+
+        assert_eq!(stats, expected);
+        assert_eq!(parallel.buffer(), serial.buffer());
+
+        // scissored fragments aren't counted by either
+        for renderer in &mut [&mut serial, &mut parallel] {
+            renderer.set_scissor(Some(Rectangle::new(20, 24, 20, 24)));
+        }
+        let expected = serial.draw(scene.clone().into_iter()).unwrap();
+        let stats = parallel.draw_parallel(scene.into_iter()).unwrap();
+        serial.swap();
+        parallel.swap();
+
+        assert_eq!(stats, expected);
+        assert!(stats.fragments > 0 && stats.fragments <= 4 * 4 * stats.shapes);
+        assert_eq!(parallel.buffer(), serial.buffer());
+    }
+
+    #[test]
+    fn clear() {
+        let mut renderer = SimpleRenderer::<u8>::with_depth(4, 4);
+        renderer.clear(7);
+        renderer.swap();
+        assert_eq!(renderer.buffer(), [7_u8; 4 * 4].as_ref());
+
+        // the default implementation
+        struct Fallback(SimpleRenderer<u8>);
+
+        impl Renderer<f64> for Fallback {
+            type Pixel = u8;
+            type Attr = u8;
+            type Error = ();
+
+            fn put_pixel(&mut self, p: Point2<usize>, px: Self::Pixel) {
+                self.0.put_pixel(p, px)
+            }
+
+            fn get_pixel(&self, p: Point2<usize>) -> Option<Self::Pixel> {
+                self.0.get_pixel(p)
+            }
+
+            fn swap(&mut self) {
+                self.0.swap()
+            }
+
+            fn width(&self) -> usize {
+                self.0.width()
+            }
+
+            fn height(&self) -> usize {
+                self.0.height()
+            }
+        }
+
+        let mut fallback = Fallback(SimpleRenderer::new(3, 2));
+        fallback.clear(5);
+        fallback.swap();
+        assert_eq!(fallback.0.buffer(), [5_u8; 3 * 2].as_ref());
+
+        assert!(renderer.depth_test((1, 1), 1.0));
+        assert!(!renderer.depth_test((1, 1), 2.0));
+        renderer.clear(0);
+        assert!(renderer.depth_test((1, 1), 2.0));
+    }
+
+    #[test]
+    fn get_pixel() {
+        let mut renderer = SimpleRenderer::<u8>::new(4, 4);
+        renderer.put_pixel((2, 1), 3);
+        assert_eq!(renderer.get_pixel((2, 1)), Some(3));
+        assert_eq!(renderer.get_pixel((1, 2)), Some(0));
+        assert_eq!(renderer.get_pixel((4, 0)), None);
+        assert_eq!(renderer.buffer()[4 + 2], 0);
+
+        let mut renderer = SimpleRenderer::<u8>::single_buffered(4, 4);
+        renderer.put_pixel((2, 1), 3);
+        assert_eq!(renderer.get_pixel((2, 1)), Some(3));
+
+        // pixels which can't be cloned nor read back can still be drawn
+        struct Opaque;
+
+        struct Counter(usize);
+
+        impl Renderer<f64> for Counter {
+            type Pixel = Opaque;
+            type Attr = Opaque;
+            type Error = ();
+
+            fn put_pixel(&mut self, _: Point2<usize>, _: Self::Pixel) {
+                self.0 += 1;
+            }
+
+            fn get_pixel(&self, _: Point2<usize>) -> Option<Self::Pixel> {
+                None
+            }
+
+            fn swap(&mut self) {}
+
+            fn width(&self) -> usize {
+                4
+            }
+
+            fn height(&self) -> usize {
+                4
+            }
+        }
+
+        let mut counter = Counter(0);
+        let rect = Rectangle::new(0.0, 2.0, 0.0, 2.0);
+        assert_eq!(counter.draw(std::iter::once(rect)).map(|stats| stats.fragments), Ok(4));
+        assert_eq!(counter.0, 0);
+    }
+
+    #[test]
+    fn attrs() {
+        let mut renderer = SimpleRenderer::<u8>::new(4, 4);
+        assert_eq!(renderer.get_attr(0), None);
+
+        renderer.set_attr(2, 3);
+        renderer.set_attr(0, 1);
+        renderer.set_attr(1, 2);
+        assert_eq!(renderer.get_attr(0), Some(1));
+        assert_eq!(renderer.get_attr(1), Some(2));
+        assert_eq!(renderer.get_attr(2), Some(3));
+        assert_eq!(renderer.get_attr(3), None);
+    }
+
+    #[test]
+    fn draw_without_attr() {
+        use std::iter;
+
+        use mesh::DepthTriangle;
+
+        let triangle = Triangle::with_points([(0.0, 0.0), (4.0, 0.0), (0.0, 4.0)]);
+        let near = DepthTriangle::new(triangle, [1.0; 3]);
+        let far = DepthTriangle::new(triangle, [2.0; 3]);
+
+        // fragments are counted and depth tested, but nothing is drawn
+        let mut renderer = SimpleRenderer::<u8>::with_depth(4, 4);
+        let stats = renderer.draw(iter::once(near)).unwrap();
+        assert_eq!((stats.shapes, stats.vertices), (1, 3));
+        assert!(stats.fragments > 0);
+        assert_eq!(renderer.get_pixel((0, 0)), Some(0));
+
+        renderer.set_attr(0, 1);
+        assert_eq!(renderer.draw(iter::once(far)).unwrap().fragments, 0);
+        assert_eq!(renderer.get_pixel((0, 0)), Some(0));
+    }
+
+    #[test]
+    fn strict() {
+        use std::iter;
+        use rect::Rectangle;
+
+        let mut renderer = SimpleRenderer::<u8>::new(4, 4);
+        renderer.set_attr(0, 1);
+        let empty = || iter::empty::<Rectangle<f64>>();
+        assert_eq!(renderer.draw(empty()), Ok(Stats::default()));
+
+        renderer.set_strict(true);
+        assert_eq!(renderer.draw(empty()), Err(RenderError::EmptyMesh));
+
+        let stats = renderer.draw(iter::once(Rectangle::new(0.0, 2.0, 0.0, 2.0)));
+        assert_eq!(
+            stats,
+            Ok(Stats {
+                shapes: 1,
+                vertices: 4,
+                fragments: 4,
+            })
+        );
+    }
+
+    #[test]
+    fn draw_culled() {
+        use tri::{Triangle, Winding};
+
+        let ccw = Triangle::with_points([(0.0, 0.0), (4.0, 0.0), (0.0, 4.0)]);
+        let cw = Triangle::with_points([(4.0, 4.0), (4.0, 0.0), (0.0, 4.0)]);
+
+        for &(front, lit, culled) in &[(Winding::Ccw, 0, 15), (Winding::Cw, 15, 0)] {
+            let mut renderer = SimpleRenderer::<u8>::new(4, 4);
+            renderer.set_attr(0, 1);
+            let stats = renderer.draw_culled(vec![ccw, cw].into_iter(), front);
+            assert_eq!(stats.map(|stats| stats.shapes), Ok(1));
+
+            renderer.swap();
+            assert_eq!(renderer.buffer()[lit], 1);
+            assert_eq!(renderer.buffer()[culled], 0);
+        }
+    }
+
+    #[test]
+    fn origin() {
+        use std::iter;
+        use rect::Rectangle;
+
+        let mut renderer = SimpleRenderer::<u8>::new(4, 4);
+
+        renderer.set_attr(0, 1);
+        assert!(renderer.draw(iter::once(Rectangle::new(0.0, 1.0, 0.0, 1.0))).is_ok());
+
+        renderer.swap();
+        assert_eq!(renderer.buffer()[0], 1);
+        assert_eq!(renderer.buffer().iter().filter(|&&px| px == 1).count(), 1);
+    }
+
+    #[test]
+    fn depth() {
+        use tri::Triangle;
+
+        struct Flat(Triangle<f64>, f64);
+
+        struct FlatCoord(Point2<f64>, f64);
+
+        impl Coord<f64> for FlatCoord {
+            fn point(&self) -> Point2<f64> {
+                self.0
+            }
+
+            fn depth(&self) -> Option<f64> {
+                Some(self.1)
+            }
+        }
+
+        impl IntoIterator for Flat {
+            type Item = FlatCoord;
+            type IntoIter = Box<dyn Iterator<Item = FlatCoord>>;
+
+            fn into_iter(self) -> Self::IntoIter {
+                let z = self.1;
+                Box::new(self.0.into_iter().map(move |(p, _)| FlatCoord(p, z)))
+            }
+        }
+
+        impl Drawable<f64, FlatCoord> for Flat {
+            fn vertices(&self) -> usize {
+                3
+            }
+        }
+
+        let near = || Flat(Triangle::with_points([(1.0, 1.0), (8.0, 1.0), (1.0, 8.0)]), 1.0);
+        let far = || Flat(Triangle::with_points([(1.0, 1.0), (8.0, 1.0), (8.0, 8.0)]), 2.0);
+
+        let mut renderer = SimpleRenderer::<u8>::with_depth(10, 10);
+        renderer.set_attr(0, 1);
+        assert!(renderer.draw(Some(near()).into_iter()).is_ok());
+        renderer.set_attr(0, 2);
+        assert!(renderer.draw(Some(far()).into_iter()).is_ok());
+        renderer.swap();
+        assert_eq!(renderer.buffer()[2 * 10 + 6], 1);
+        assert_eq!(renderer.buffer()[6 * 10 + 7], 2);
+
+        let mut renderer = SimpleRenderer::<u8>::with_depth(10, 10);
+        renderer.set_attr(0, 2);
+        assert!(renderer.draw(Some(far()).into_iter()).is_ok());
+        renderer.set_attr(0, 1);
+        assert!(renderer.draw(Some(near()).into_iter()).is_ok());
+        renderer.swap();
+        assert_eq!(renderer.buffer()[2 * 10 + 6], 1);
+        assert_eq!(renderer.buffer()[6 * 10 + 7], 2);
+    }
+
+    #[test]
+    fn single_buffered() {
+        use std::iter;
+        use tri::Triangle;
+
+        let mut renderer = SimpleRenderer::<u8>::single_buffered(16, 16);
+        let triangle = Triangle::with_points([(0.0, 0.0), (100.0, 0.0), (0.0, 100.0)]);
+
         renderer.set_attr(0, 1);
         assert!(renderer.draw(iter::once(triangle)).is_ok());
+        assert_eq!(renderer.buffer(), [1_u8; 16 * 16].as_ref());
 
         renderer.swap();
         assert_eq!(renderer.buffer(), [1_u8; 16 * 16].as_ref());
     }
+
+    #[test]
+    fn resize() {
+        use std::iter;
+
+        let mut renderer = SimpleRenderer::<u8>::with_depth(4, 4);
+        renderer.set_attr(0, 1);
+        assert!(renderer.draw(iter::once(Rectangle::new(1.0, 3.0, 1.0, 3.0))).is_ok());
+        renderer.swap();
+
+        renderer.resize(6, 5);
+        assert_eq!(Renderer::<f64>::width(&renderer), 6);
+        assert_eq!(Renderer::<f64>::height(&renderer), 5);
+        #[rustfmt::skip]
+        assert_eq!(renderer.buffer(), [
+            0, 0, 0, 0, 0, 0,
+            0, 1, 1, 0, 0, 0,
+            0, 1, 1, 0, 0, 0,
+            0, 0, 0, 0, 0, 0,
+            0, 0, 0, 0, 0, 0,
+        ].as_ref());
+
+        // the attribute survives, and the new area can be drawn to
+        assert!(renderer.draw(iter::once(Rectangle::new(4.0, 6.0, 3.0, 5.0))).is_ok());
+        renderer.swap();
+        assert_eq!(renderer.buffer()[4 * 6 + 5], 1);
+
+        // both buffers are truncated
+        renderer.resize(2, 2);
+        assert_eq!(renderer.buffer(), [0; 4].as_ref());
+        assert_eq!(Renderer::<f64>::get_pixel(&renderer, (1, 1)), Some(1));
+    }
+
+    #[test]
+    fn measure() {
+        use mesh::DepthTriangle;
+        use shape::Shape;
+
+        // partially out of bounds
+        let tri = Triangle::with_points([(0.0, 0.0), (12.0, 0.0), (0.0, 12.0)]);
+        let rect = Rectangle::new(2.0, 6.0, 2.0, 6.0);
+        let scene = || vec![Shape::from(tri), Shape::from(rect)].into_iter();
+
+        let mut renderer = SimpleRenderer::<u8, f64>::with_stencil(8, 8);
+        renderer.set_stencil((3, 3), 1);
+        renderer.set_stencil_func(StencilFunc::NotEqual(1));
+        let stats = renderer.measure(scene());
+        assert_eq!((stats.shapes, stats.vertices), (2, 7));
+        assert!(stats.fragments > 0);
+        // also without an attribute
+        assert_eq!(Ok(stats), renderer.draw(scene()));
+
+        renderer.set_attr(0, 1);
+        assert_eq!(Ok(stats), renderer.draw(scene()));
+        renderer.swap();
+        assert_eq!(renderer.buffer()[0], 1);
+
+        // occluded fragments are counted too
+        let near = DepthTriangle::new(tri, [1.0; 3]);
+        let far = DepthTriangle::new(tri, [2.0; 3]);
+        let mut renderer = SimpleRenderer::<u8, f64>::with_depth(8, 8);
+        let near_stats = renderer.measure(Some(near).into_iter());
+        let stats = renderer.measure(vec![near, far].into_iter());
+        assert_eq!(stats.fragments, 2 * near_stats.fragments);
+
+        let drawn = renderer.draw(vec![near, far].into_iter()).unwrap();
+        assert_eq!((drawn.shapes, drawn.vertices), (stats.shapes, stats.vertices));
+        assert_eq!(drawn.fragments, near_stats.fragments);
+    }
+
+    #[test]
+    fn viewport() {
+        use std::iter;
+
+        use mesh::DepthTriangle;
+
+        // only the pixels within the buffer are visited
+        let far = Triangle::with_points([(0.0, 0.0), (1e10, 0.0), (0.0, 1e10)]);
+        let mut renderer = SimpleRenderer::<u8>::with_depth(8, 8);
+        renderer.set_attr(0, 1);
+        assert_eq!(renderer.draw(iter::once(far)).map(|s| s.fragments), Ok(64));
+        let far = DepthTriangle::new(far, [1.0; 3]);
+        renderer.clear(0);
+        assert_eq!(renderer.draw(iter::once(far)).map(|s| s.fragments), Ok(64));
+        renderer.swap();
+        assert_eq!(renderer.buffer(), [1; 64].as_ref());
+    }
+
+    #[test]
+    fn scissor() {
+        use std::iter;
+
+        let mut renderer = SimpleRenderer::<u8>::new(8, 8);
+        let rect = Rectangle::new(0.0, 8.0, 0.0, 8.0);
+
+        renderer.set_scissor(Some(Rectangle::new(0, 4, 0, 4)));
+        renderer.set_attr(0, 1);
+        assert_eq!(renderer.measure(iter::once(rect)).fragments, 4 * 4);
+        assert_eq!(renderer.draw(iter::once(rect)).map(|s| s.fragments), Ok(4 * 4));
+        renderer.swap();
+
+        for (i, &px) in renderer.buffer().iter().enumerate() {
+            let inside = i % 8 < 4 && i / 8 < 4;
+            assert_eq!(px, inside as u8);
+        }
+
+        renderer.set_scissor(None);
+        assert!(renderer.draw(iter::once(rect)).is_ok());
+        renderer.swap();
+        assert_eq!(renderer.buffer(), [1_u8; 8 * 8].as_ref());
+    }
+
+    #[test]
+    fn stencil() {
+        use std::iter;
+
+        let mut renderer = SimpleRenderer::<u8>::with_stencil(8, 8);
+        let rect = Rectangle::new(0.0, 8.0, 0.0, 8.0);
+        for y in 0..8 {
+            for x in 0..4 {
+                renderer.set_stencil((x, y), 1);
+            }
+        }
+        renderer.set_stencil((8, 0), 1);
+
+        renderer.set_stencil_func(StencilFunc::Equal(1));
+        renderer.set_attr(0, 1);
+        assert_eq!(renderer.draw(iter::once(rect)).unwrap().fragments, 32);
+        renderer.swap();
+        for (i, &px) in renderer.buffer().iter().enumerate() {
+            assert_eq!(px, (i % 8 < 4) as u8);
+        }
+
+        // drawn to the other, blank buffer
+        renderer.set_stencil_func(StencilFunc::Less(1));
+        renderer.set_attr(0, 2);
+        assert!(renderer.draw(iter::once(rect)).is_ok());
+        renderer.swap();
+        for (i, &px) in renderer.buffer().iter().enumerate() {
+            assert_eq!(px, if i % 8 < 4 { 0 } else { 2 });
+        }
+
+        // without a stencil buffer, everything passes
+        let mut renderer = SimpleRenderer::<u8>::new(8, 8);
+        renderer.set_stencil((0, 0), 1);
+        renderer.set_stencil_func(StencilFunc::Never);
+        renderer.set_attr(0, 1);
+        assert_eq!(renderer.draw(iter::once(rect)).unwrap().fragments, 64);
+
+        #[cfg(feature = "rayon")]
+        {
+            let mut renderer = SimpleRenderer::<u8>::with_stencil(8, 8);
+            renderer.set_stencil((3, 5), 1);
+            renderer.set_stencil_func(StencilFunc::NotEqual(1));
+            renderer.set_attr(0, 1);
+            let stats = renderer.draw_parallel(iter::once(rect)).unwrap();
+            assert_eq!(stats.fragments, 63);
+        }
+    }
+
+    #[test]
+    fn blit() {
+        let mut src = SimpleRenderer::<u8>::new(6, 6);
+        for (i, px) in src.buffer_mut().iter_mut().enumerate() {
+            *px = i as u8 + 1;
+        }
+
+        let mut dst = SimpleRenderer::<u8>::new(8, 8);
+        dst.blit_from(&src, Rectangle::new(1, 5, 2, 6), (3, 2));
+        for y in 0..8 {
+            for x in 0..8 {
+                // offset by (2, 0) from the source
+                let expected = if (3..7).contains(&x) && (2..6).contains(&y) {
+                    src.buffer()[y * 6 + x - 2]
+                } else {
+                    0
+                };
+                assert_eq!(dst.buffer()[y * 8 + x], expected, "at {:?}", (x, y));
+            }
+        }
+
+        // clipped at the bottom right of the destination
+        let mut dst = SimpleRenderer::<u8>::new(8, 8);
+        dst.blit_from(&src, Rectangle::new(0, 4, 0, 4), (6, 7));
+        let written = dst.buffer().iter().filter(|&&px| px != 0).collect::<Vec<_>>();
+        assert_eq!(written, [&1, &2]);
+
+        // entirely off-screen, and a source rect past the source bounds
+        dst.blit_from(&src, Rectangle::new(0, 4, 0, 4), (8, 0));
+        dst.blit_from(&src, Rectangle::new(4, 10, 4, 10), (0, 0));
+        assert_eq!(&dst.buffer()[..3], &[29, 30, 0]);
+        assert_eq!(&dst.buffer()[8..10], &[35, 36]);
+    }
+
+    #[test]
+    fn flood_fill() {
+        use line::Line;
+
+        let mut renderer = SimpleRenderer::<u8>::single_buffered(8, 8);
+        // the top left corner is missing, but only touches the inside
+        // diagonally
+        let outline = vec![
+            Line::new((2.0, 1.0), (6.0, 1.0)),
+            Line::new((1.0, 2.0), (1.0, 6.0)),
+            Line::new((1.0, 6.0), (6.0, 6.0)),
+            Line::new((6.0, 1.0), (6.0, 6.0)),
+        ];
+        renderer.set_attr(0, 1);
+        assert!(renderer.draw(outline.into_iter()).is_ok());
+        let before = renderer.buffer().to_vec();
+
+        renderer.flood_fill((3, 4), 2);
+        assert_eq!(before[8 + 1], 0);
+        for y in 0..8 {
+            for x in 0..8 {
+                let expected = if (2..6).contains(&x) && (2..6).contains(&y) {
+                    2
+                } else {
+                    before[y * 8 + x]
+                };
+                assert_eq!(renderer.buffer()[y * 8 + x], expected, "at {:?}", (x, y));
+            }
+        }
+
+        // filling with the same color does nothing
+        renderer.flood_fill((3, 4), 2);
+        assert_eq!(renderer.buffer().iter().filter(|&&px| px == 2).count(), 16);
+    }
+
+    #[test]
+    fn flood_fill_blank() {
+        let mut renderer = SimpleRenderer::<u8>::new(64, 48);
+        renderer.flood_fill((63, 47), 7);
+        renderer.flood_fill((64, 0), 9);
+        renderer.swap();
+        assert_eq!(renderer.buffer(), [7_u8; 64 * 48].as_ref());
+    }
+
+    #[test]
+    fn downsample() {
+        let (black, white) = ((0, 0, 0), (255, 255, 255));
+        let mut renderer = SimpleRenderer::new(5, 5);
+        for (i, px) in renderer.buffer_mut().iter_mut().enumerate() {
+            *px = if (i % 5 + i / 5) % 2 == 0 { white } else { black };
+        }
+
+        // the last row and column are truncated
+        let small = renderer.downsample(2);
+        assert_eq!((small.width(), small.height()), (2, 2));
+        assert_eq!(small.buffer(), [(188, 188, 188); 4].as_ref());
+
+        assert_eq!(renderer.downsample(1).buffer(), renderer.buffer());
+        assert_eq!(renderer.downsample(5).buffer().len(), 1);
+        assert!(renderer.downsample(6).buffer().is_empty());
+    }
+
+    #[test]
+    fn tone_map() {
+        use std::iter;
+        use rect::Rectangle;
+
+        let mut renderer = SimpleRenderer::single_buffered(2, 1);
+        renderer.set_blend(BlendMode::Add);
+        renderer.set_attr(0, (1.0, 1.0, 1.0));
+        let rect = Rectangle::new(0.0, 1.0, 0.0, 1.0);
+        assert!(renderer.draw(iter::once(rect)).is_ok());
+        assert!(renderer.draw(iter::once(rect)).is_ok());
+        assert_eq!(renderer.buffer(), [(2.0, 2.0, 2.0), (0.0, 0.0, 0.0)].as_ref());
+
+        let ldr = renderer.tone_map(1.0);
+        let (bright, black) = (ldr.buffer()[0], ldr.buffer()[1]);
+        assert_eq!(black, (0, 0, 0));
+        assert_eq!(bright, (bright.0, bright.0, bright.0));
+        assert!(bright.0 > 200 && bright.0 < 255);
+        assert!(renderer.tone_map(4.0).buffer()[0].0 > bright.0);
+    }
+
+    #[test]
+    fn clamp_attr() {
+        use std::iter;
+        use tri::Triangle;
+
+        struct Level(i32);
+
+        impl From<Level> for u8 {
+            fn from(level: Level) -> u8 {
+                level.0 as u8
+            }
+        }
+
+        struct Saturating(SimpleRenderer<u8>, i32);
+
+        impl Renderer<f64> for Saturating {
+            type Pixel = u8;
+            type Attr = Level;
+            type Error = ();
+
+            fn put_pixel(&mut self, p: Point2<usize>, px: Self::Pixel) {
+                self.0.put_pixel(p, px)
+            }
+
+            fn get_pixel(&self, p: Point2<usize>) -> Option<Self::Pixel> {
+                self.0.get_pixel(p)
+            }
+
+            fn swap(&mut self) {
+                self.0.swap()
+            }
+
+            fn width(&self) -> usize {
+                self.0.width()
+            }
+
+            fn height(&self) -> usize {
+                self.0.height()
+            }
+
+            fn get_attr(&self, _attr: usize) -> Option<Self::Attr> {
+                Some(Level(self.1))
+            }
+
+            fn clamp_attr(&self, attr: Self::Attr) -> Self::Attr {
+                Level(attr.0.clamp(0, 255))
+            }
+        }
+
+        let mut renderer = Saturating(SimpleRenderer::new(4, 4), 300);
+        let triangle = Triangle::with_points([(1.0, 1.0), (3.0, 1.0), (1.0, 3.0)]);
+        assert_eq!(u8::from(Level(300)), 44);
+        assert!(renderer.draw(iter::once(triangle)).is_ok());
+
+        renderer.swap();
+        assert_eq!(renderer.0.buffer()[4 + 1], 255);
+    }
+
+    #[test]
+    fn builder() {
+        use std::iter;
+
+        use mesh::DepthTriangle;
+        use tri::Triangle;
+
+        let near = Triangle::with_points([(0.0, 0.0), (8.0, 0.0), (0.0, 8.0)]);
+        let far = Triangle::with_points([(0.0, 0.0), (8.0, 0.0), (8.0, 8.0)]);
+        let scene = [
+            DepthTriangle::new(near, [1.0; 3]),
+            DepthTriangle::new(far, [2.0; 3]),
+        ];
+        let render = |renderer: &mut SimpleRenderer<(u8, u8, u8, u8)>| {
+            renderer.set_attr(0, (255, 0, 0, 128));
+            assert!(renderer.draw(scene.iter().cloned()).is_ok());
+            renderer.swap();
+            assert_eq!(renderer.draw(iter::empty::<Rectangle<f64>>()), Err(RenderError::EmptyMesh));
+            renderer.buffer().to_vec()
+        };
+
+        let scissor = Some(Rectangle::new(0, 6, 0, 8));
+        let mut built = SimpleRendererBuilder::new()
+            .depth(true)
+            .blend(BlendMode::Over)
+            .scissor(scissor)
+            .strict(true)
+            .build(8, 8);
+        let mut manual = SimpleRenderer::with_depth(8, 8);
+        manual.set_blend(BlendMode::Over);
+        manual.set_scissor(scissor);
+        manual.set_strict(true);
+        let buffer = render(&mut built);
+        assert_eq!(buffer, render(&mut manual));
+        // blended over the transparent buffer with the far triangle hidden,
+        // and cut off by the scissor
+        assert_eq!(buffer[8 + 1], (255, 0, 0, 128));
+        assert_eq!(buffer[8 + 7], (0, 0, 0, 0));
+
+        let mut built = SimpleRendererBuilder::new()
+            .stencil(true)
+            .stencil_func(StencilFunc::Equal(1))
+            .build(8, 8);
+        let mut manual = SimpleRenderer::<u8>::with_stencil(8, 8);
+        manual.set_stencil_func(StencilFunc::Equal(1));
+        for renderer in [&mut built, &mut manual].iter_mut() {
+            renderer.set_stencil((1, 1), 1);
+            renderer.set_attr(0, 1);
+            let stats = renderer.draw(iter::once(Rectangle::new(0.0, 8.0, 0.0, 8.0)));
+            assert_eq!(stats.map(|stats| stats.fragments), Ok(1));
+        }
+        assert_eq!(built.buffer(), manual.buffer());
+
+        let mut built = SimpleRendererBuilder::new().single_buffered(true).build(4, 4);
+        built.set_attr(0, 1_u8);
+        assert!(built.draw(iter::once(Rectangle::new(0.0, 4.0, 0.0, 4.0))).is_ok());
+        assert_eq!(built.buffer(), [1; 16].as_ref());
+
+        let mut built = SimpleRendererBuilder::new().msaa(2).build(4, 4);
+        assert_eq!((Renderer::<f64>::width(&built), Renderer::<f64>::height(&built)), (8, 8));
+        built.set_attr(0, (255, 255, 255));
+        let triangle = Triangle::with_points([(0.0, 0.0), (8.0, 0.0), (0.0, 8.0)]);
+        assert!(built.draw(iter::once(triangle)).is_ok());
+        built.swap();
+        let image = built.downsample(2);
+        assert_eq!(image.buffer()[0], (255, 255, 255));
+        // partially covered on the diagonal
+        assert!(image.buffer()[2 * 4 + 2].0 > 0 && image.buffer()[2 * 4 + 2].0 < 255);
+        assert_eq!(image.buffer()[15], (0, 0, 0));
+    }
+
+    #[test]
+    fn draw_ids() {
+        use mesh::DepthTriangle;
+        use tri::Triangle;
+
+        let near = Triangle::with_points([(0.0, 0.0), (8.0, 0.0), (0.0, 8.0)]);
+        let far = Triangle::with_points([(0.0, 0.0), (8.0, 0.0), (8.0, 8.0)]);
+        let near = (DepthTriangle::new(near, [1.0; 3]), 7);
+        let far = (DepthTriangle::new(far, [2.0; 3]), 9);
+
+        for scene in &[[near, far], [far, near]] {
+            let mut renderer = SimpleRenderer::<u8>::with_depth(8, 8);
+            assert_eq!(renderer.id_at((1, 1)), None);
+            let stats = renderer.draw_ids(scene.iter().map(|s| s.0), |i| scene[i].1);
+            assert_eq!(stats.map(|stats| stats.shapes), Ok(2));
+
+            // overlapping, only far, only near and neither
+            assert_eq!(renderer.id_at((2, 1)), Some(7));
+            assert_eq!(renderer.id_at((6, 5)), Some(9));
+            assert_eq!(renderer.id_at((1, 6)), Some(7));
+            assert_eq!(renderer.id_at((8, 0)), None);
+            assert_eq!(renderer.get_pixel((2, 1)), Some(0));
+
+            renderer.clear(0);
+            assert_eq!(renderer.id_at((2, 1)), None);
+        }
+    }
+
+    #[test]
+    fn splats() {
+        let mut renderer = SimpleRenderer::<(u8, u8, u8)>::new(4, 4);
+
+        renderer.splat_accumulate((1.0, 1.0), (255, 0, 0), 0.5);
+        renderer.splat_accumulate((1.5, 1.5), (0, 0, 255), 0.5);
+        renderer.splat_accumulate((2.0, 1.0), (0, 255, 0), 0.25);
+        renderer.resolve_splats();
+
+        renderer.swap();
+        assert_eq!(renderer.buffer()[5], (128, 0, 128));
+        assert_eq!(renderer.buffer()[6], (0, 255, 0));
+        assert_eq!(renderer.buffer()[0], (0, 0, 0));
+    }
 }
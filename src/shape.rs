@@ -1,22 +1,36 @@
+use std::iter::Flatten;
+use std::vec;
+
 use num_traits::{AsPrimitive, Float};
-use line_drawing::{FloatNum, SignedNum};
+use line_drawing::FloatNum;
 
 use renderer::{Coord, Drawable};
-use point::{self, Point, Point2};
+use point::{self, FatPoint, Point, Point2};
 use line::{self, Line};
 use rect::{self, Rectangle};
 use tri::{self, Triangle};
+use circle::{self, Circle};
+use capsule::{self, Capsule};
+use ellipse::{self, Ellipse};
+use polygon::{self, Polygon};
 
 /// A dynamic primitive over all the other primitives.
-#[derive(Clone, Copy, Debug)]
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "serde", serde(tag = "type", content = "shape"))]
 pub enum Shape<T> {
     Point(Point<T>),
+    FatPoint(FatPoint<T>),
     Line(Line<T>),
     Rect(Rectangle<T>),
     Tri(Triangle<T>),
+    Circle(Circle<T>),
+    Capsule(Capsule<T>),
+    Ellipse(Ellipse<T>),
+    Polygon(Polygon<T>),
 }
 
-impl<T: Float + FloatNum + SignedNum + AsPrimitive<i64> + Copy + 'static> Drawable<T, Point2<T>>
+impl<T: Float + FloatNum + AsPrimitive<i64> + Copy + 'static> Drawable<T, Point2<T>>
     for Shape<T>
 where
     i64: AsPrimitive<T>,
@@ -25,9 +39,49 @@ where
     fn vertices(&self) -> usize {
         match *self {
             Shape::Point(ref point) => point.vertices(),
+            Shape::FatPoint(ref point) => point.vertices(),
             Shape::Line(ref line) => line.vertices(),
             Shape::Rect(ref rect) => rect.vertices(),
             Shape::Tri(ref tri) => tri.vertices(),
+            Shape::Circle(ref circle) => circle.vertices(),
+            Shape::Capsule(ref capsule) => capsule.vertices(),
+            Shape::Ellipse(ref ellipse) => ellipse.vertices(),
+            Shape::Polygon(ref polygon) => polygon.vertices(),
+        }
+    }
+}
+
+impl<T: Float + FloatNum + AsPrimitive<i64>> Shape<T> {
+    /// Get the smallest `Rectangle` containing the shape, i.e. for spatial
+    /// culling.
+    #[inline]
+    pub fn bounding_box(&self) -> Rectangle<T> {
+        match *self {
+            Shape::Point(ref point) => point.bounding_box(),
+            Shape::FatPoint(ref point) => point.bounding_box(),
+            Shape::Line(ref line) => line.bounding_box(),
+            Shape::Rect(ref rect) => rect.bounding_box(),
+            Shape::Tri(ref tri) => tri.bounding_box(),
+            Shape::Circle(ref circle) => circle.bounding_box(),
+            Shape::Capsule(ref capsule) => capsule.bounding_box(),
+            Shape::Ellipse(ref ellipse) => ellipse.bounding_box(),
+            Shape::Polygon(ref polygon) => polygon.bounding_box(),
+        }
+    }
+}
+
+impl<T: Float + FloatNum + AsPrimitive<i64> + Copy + 'static> Shape<T>
+where
+    i64: AsPrimitive<T>,
+{
+    /// Iterate over the pixels of the shape like `into_iter`, but keep the
+    /// barycentric coordinates of lines and triangles, i.e. to interpolate
+    /// attributes in `draw_with`.
+    #[inline]
+    pub fn into_coords(self) -> Coords<T> {
+        Coords {
+            vertices: self.vertices(),
+            inner: self.into_iter(),
         }
     }
 }
@@ -39,6 +93,13 @@ impl<T> From<Point<T>> for Shape<T> {
     }
 }
 
+impl<T> From<FatPoint<T>> for Shape<T> {
+    #[inline(always)]
+    fn from(point: FatPoint<T>) -> Self {
+        Shape::FatPoint(point)
+    }
+}
+
 impl<T> From<Line<T>> for Shape<T> {
     #[inline(always)]
     fn from(line: Line<T>) -> Self {
@@ -60,7 +121,35 @@ impl<T> From<Triangle<T>> for Shape<T> {
     }
 }
 
-impl<T: Float + FloatNum + SignedNum + AsPrimitive<i64> + Copy + 'static> IntoIterator for Shape<T>
+impl<T> From<Circle<T>> for Shape<T> {
+    #[inline(always)]
+    fn from(circle: Circle<T>) -> Self {
+        Shape::Circle(circle)
+    }
+}
+
+impl<T> From<Capsule<T>> for Shape<T> {
+    #[inline(always)]
+    fn from(capsule: Capsule<T>) -> Self {
+        Shape::Capsule(capsule)
+    }
+}
+
+impl<T> From<Ellipse<T>> for Shape<T> {
+    #[inline(always)]
+    fn from(ellipse: Ellipse<T>) -> Self {
+        Shape::Ellipse(ellipse)
+    }
+}
+
+impl<T> From<Polygon<T>> for Shape<T> {
+    #[inline(always)]
+    fn from(polygon: Polygon<T>) -> Self {
+        Shape::Polygon(polygon)
+    }
+}
+
+impl<T: Float + FloatNum + AsPrimitive<i64> + Copy + 'static> IntoIterator for Shape<T>
 where
     i64: AsPrimitive<T>,
 {
@@ -71,26 +160,36 @@ where
     fn into_iter(self) -> Self::IntoIter {
         match self {
             Shape::Point(point) => IntoIter::Point(point.into_iter()),
+            Shape::FatPoint(point) => IntoIter::FatPoint(point.into_iter()),
             Shape::Line(line) => IntoIter::Line(line.into_iter()),
             Shape::Rect(rect) => IntoIter::Rect(rect.into_iter()),
             Shape::Tri(tri) => IntoIter::Tri(tri.into_iter()),
+            Shape::Circle(circle) => IntoIter::Circle(circle.into_iter()),
+            Shape::Capsule(capsule) => IntoIter::Capsule(capsule.into_iter()),
+            Shape::Ellipse(ellipse) => IntoIter::Ellipse(ellipse.into_iter()),
+            Shape::Polygon(polygon) => IntoIter::Polygon(polygon.into_iter()),
         }
     }
 }
 
 /// A dynamic `Iterator` over the other primitive point `Iterator`-s.
 #[derive(Debug)]
-pub enum IntoIter<T: Float + FloatNum + SignedNum + Copy + 'static>
+pub enum IntoIter<T: Float + FloatNum + Copy + 'static>
 where
     i64: AsPrimitive<T>,
 {
     Point(point::IntoIter<T>),
+    FatPoint(point::FatIter<T>),
     Line(line::IntoIter<T>),
     Rect(rect::IntoIter<T>),
     Tri(tri::IntoIter<T>),
+    Circle(circle::IntoIter<T>),
+    Capsule(capsule::IntoIter<T>),
+    Ellipse(ellipse::IntoIter<T>),
+    Polygon(polygon::IntoIter<T>),
 }
 
-impl<T: Float + FloatNum + SignedNum + Copy + 'static> Iterator for IntoIter<T>
+impl<T: Float + FloatNum + Copy + 'static> Iterator for IntoIter<T>
 where
     i64: AsPrimitive<T>,
 {
@@ -100,9 +199,230 @@ where
     fn next(&mut self) -> Option<Self::Item> {
         match *self {
             IntoIter::Point(ref mut point) => point.next(),
+            IntoIter::FatPoint(ref mut point) => point.next(),
             IntoIter::Line(ref mut line) => line.next().map(|c| c.point()),
             IntoIter::Rect(ref mut rect) => rect.next(),
             IntoIter::Tri(ref mut tri) => tri.next().map(|c| c.point()),
+            IntoIter::Circle(ref mut circle) => circle.next(),
+            IntoIter::Capsule(ref mut capsule) => capsule.next(),
+            IntoIter::Ellipse(ref mut ellipse) => ellipse.next(),
+            IntoIter::Polygon(ref mut polygon) => polygon.next(),
+        }
+    }
+}
+
+/// A coordinate of any primitive, yielded by `Shape::into_coords`.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum ShapeCoord<T> {
+    /// A pixel of a primitive without a barycentric coordinate.
+    Point(Point2<T>),
+    Line(line::Coordinate<T>),
+    Tri(tri::Coordinate<T>),
+}
+
+impl<T: Copy> Coord<T> for ShapeCoord<T> {
+    #[inline(always)]
+    fn point(&self) -> Point2<T> {
+        match *self {
+            ShapeCoord::Point(p) => p,
+            ShapeCoord::Line(ref c) => c.point(),
+            ShapeCoord::Tri(ref c) => c.point(),
+        }
+    }
+
+    #[inline(always)]
+    fn barycentric(&self) -> Option<&[T]> {
+        match *self {
+            ShapeCoord::Point(_) => None,
+            ShapeCoord::Line(ref c) => c.barycentric(),
+            ShapeCoord::Tri(ref c) => c.barycentric(),
+        }
+    }
+}
+
+/// An `Iterator` over the `ShapeCoord`-s of a `Shape`, which can be drawn
+/// itself.
+///
+/// See `Shape::into_coords`.
+#[derive(Debug)]
+pub struct Coords<T: Float + FloatNum + Copy + 'static>
+where
+    i64: AsPrimitive<T>,
+{
+    vertices: usize,
+    inner: IntoIter<T>,
+}
+
+impl<T: Float + FloatNum + Copy + 'static> Iterator for Coords<T>
+where
+    i64: AsPrimitive<T>,
+{
+    type Item = ShapeCoord<T>;
+
+    #[inline]
+    fn next(&mut self) -> Option<Self::Item> {
+        match self.inner {
+            IntoIter::Point(ref mut point) => point.next().map(ShapeCoord::Point),
+            IntoIter::FatPoint(ref mut point) => point.next().map(ShapeCoord::Point),
+            IntoIter::Line(ref mut line) => line.next().map(ShapeCoord::Line),
+            IntoIter::Rect(ref mut rect) => rect.next().map(ShapeCoord::Point),
+            IntoIter::Tri(ref mut tri) => tri.next().map(ShapeCoord::Tri),
+            IntoIter::Circle(ref mut circle) => circle.next().map(ShapeCoord::Point),
+            IntoIter::Capsule(ref mut capsule) => capsule.next().map(ShapeCoord::Point),
+            IntoIter::Ellipse(ref mut ellipse) => ellipse.next().map(ShapeCoord::Point),
+            IntoIter::Polygon(ref mut polygon) => polygon.next().map(ShapeCoord::Point),
+        }
+    }
+}
+
+impl<T: Float + FloatNum + Copy + 'static> Drawable<T, ShapeCoord<T>> for Coords<T>
+where
+    i64: AsPrimitive<T>,
+{
+    #[inline(always)]
+    fn vertices(&self) -> usize {
+        self.vertices
+    }
+}
+
+/// A composition of dynamic primitives, drawn as a single primitive.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Scene<T>(pub Vec<Shape<T>>);
+
+impl<T: Float + FloatNum + AsPrimitive<i64> + Copy + 'static> Drawable<T, Point2<T>> for Scene<T>
+where
+    i64: AsPrimitive<T>,
+{
+    #[inline]
+    fn vertices(&self) -> usize {
+        self.0.iter().map(|shape| shape.vertices()).sum()
+    }
+}
+
+impl<T: Float + FloatNum + AsPrimitive<i64> + Copy + 'static> IntoIterator for Scene<T>
+where
+    i64: AsPrimitive<T>,
+{
+    type Item = Point2<T>;
+    type IntoIter = Flatten<vec::IntoIter<Shape<T>>>;
+
+    #[inline]
+    fn into_iter(self) -> Self::IntoIter {
+        self.0.into_iter().flatten()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::BTreeSet;
+
+    use renderer::Drawable;
+    use rect::Rectangle;
+    use tri::Triangle;
+    use super::Scene;
+
+    #[test]
+    fn scene() {
+        let tri = Triangle::with_points([(0.0, 0.0), (6.0, 0.0), (0.0, 6.0)]);
+        let rect = Rectangle::new(4.0, 8.0, 4.0, 8.0);
+        let scene = Scene(vec![tri.into(), rect.into()]);
+        assert_eq!(scene.vertices(), 7);
+
+        let expected = tri
+            .into_iter()
+            .map(|(p, _)| p)
+            .chain(rect)
+            .map(|(x, y)| (x as i64, y as i64))
+            .collect::<BTreeSet<_>>();
+        let fragments = scene
+            .into_iter()
+            .map(|(x, y)| (x as i64, y as i64))
+            .collect::<BTreeSet<_>>();
+
+        assert_eq!(fragments, expected);
+    }
+
+    #[test]
+    fn bounding_box() {
+        use capsule::Capsule;
+        use circle::Circle;
+        use ellipse::Ellipse;
+        use line::Line;
+        use point::Point;
+        use polygon::Polygon;
+        use super::Shape;
+
+        let cases: Vec<(Shape<f64>, _)> = vec![
+            (Point((3.0, 4.0)).into(), (3.0, 3.0, 4.0, 4.0)),
+            (Point::fat((3.0, 4.0), 2.0).into(), (1.0, 5.0, 2.0, 6.0)),
+            // from the bottom right to the top left
+            (Line::new((8.0, 7.0), (1.0, 2.0)).into(), (1.0, 8.0, 2.0, 7.0)),
+            (Line::with_width((1.0, 2.0), (8.0, 2.0), 2.0).into(), (0.0, 9.0, 1.0, 3.0)),
+            (Rectangle::new(5.0, 1.0, 6.0, 2.0).into(), (1.0, 5.0, 2.0, 6.0)),
+            (
+                Triangle::with_points([(4.0, 1.0), (9.0, 6.0), (2.0, 5.0)]).into(),
+                (2.0, 9.0, 1.0, 6.0),
+            ),
+            (Circle::new((5.0, 5.0), 2.0).into(), (3.0, 7.0, 3.0, 7.0)),
+            (Capsule::new((6.0, 5.0), (2.0, 3.0), 1.0).into(), (1.0, 7.0, 2.0, 6.0)),
+            (Ellipse::new((5.0, 5.0), 3.0, 1.0).into(), (2.0, 8.0, 4.0, 6.0)),
+            (
+                Polygon::new(vec![(0.0, 1.0), (4.0, -2.0), (6.0, 3.0)]).into(),
+                (0.0, 6.0, -2.0, 3.0),
+            ),
+        ];
+
+        for (shape, (x0, x1, y0, y1)) in cases {
+            assert_eq!(shape.bounding_box(), Rectangle::new(x0, x1, y0, y1));
         }
     }
+
+    #[test]
+    fn into_coords() {
+        use renderer::Coord;
+        use super::{Shape, ShapeCoord};
+
+        let tri = Triangle::with_points([(0.0, 0.0), (6.0, 0.0), (0.0, 6.0)]);
+        let coords = Shape::from(tri).into_coords();
+        assert_eq!(coords.vertices(), 3);
+        let coords = coords.collect::<Vec<_>>();
+        let expected = tri.into_iter().map(ShapeCoord::Tri).collect::<Vec<_>>();
+        assert!(!expected.is_empty());
+        assert_eq!(coords, expected);
+        let (_, weights) = tri.into_iter().next().unwrap();
+        assert_eq!(coords[0].barycentric(), Some(&weights[..]));
+
+        let mut rect = Shape::from(Rectangle::new(0.0, 2.0, 0.0, 2.0)).into_coords();
+        assert!(rect.all(|c| c.barycentric().is_none()));
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn serde() {
+        use serde_json;
+
+        use capsule::Capsule;
+        use circle::Circle;
+        use ellipse::Ellipse;
+        use line::Line;
+        use point::Point;
+        use polygon::Polygon;
+        use super::Shape;
+
+        let shapes: Vec<Shape<f64>> = vec![
+            Point((1.0, 2.0)).into(),
+            Point::fat_round((1.0, 2.0), 1.5).into(),
+            Line::with_width((0.0, 0.0), (4.0, 3.0), 2.0).into(),
+            Rectangle::new(0.0, 4.0, 1.0, 5.0).into(),
+            Triangle::with_points([(0.0, 0.0), (6.0, 0.0), (0.0, 6.0)]).into(),
+            Circle::new((3.0, 3.0), 2.5).into(),
+            Capsule::new((1.0, 1.0), (4.0, 2.0), 1.5).into(),
+            Ellipse::new((3.0, 3.0), 2.0, 1.0).into(),
+            Polygon::new(vec![(0.0, 0.0), (4.0, 0.0), (2.0, 3.0)]).into(),
+        ];
+
+        let json = serde_json::to_string(&shapes).unwrap();
+        assert!(json.contains(r#""type":"Tri""#));
+        assert_eq!(serde_json::from_str::<Vec<Shape<f64>>>(&json).unwrap(), shapes);
+    }
 }
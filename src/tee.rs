@@ -0,0 +1,145 @@
+//! A renderer mirroring every draw onto two targets.
+
+use num_traits::{AsPrimitive, Signed};
+
+use renderer::Renderer;
+use point::Point2;
+
+/// A `Renderer` which forwards everything to a primary and a secondary
+/// renderer, i.e. for split-screen views or preview thumbnails.
+///
+/// The size of the `TeeRenderer` is the size of the primary renderer.
+/// Pixels put to the secondary renderer are scaled to its size using nearest
+/// neighbour, so a secondary renderer larger than the primary one will have
+/// gaps between the mirrored pixels.
+///
+/// # Parameters
+///
+/// - `A` represents the primary renderer.
+/// - `B` represents the secondary renderer.
+#[derive(Clone, Debug)]
+pub struct TeeRenderer<A, B> {
+    primary: A,
+    secondary: B,
+}
+
+impl<A, B> TeeRenderer<A, B> {
+    /// Create a new `TeeRenderer` forwarding to `primary` and `secondary`.
+    #[inline(always)]
+    pub fn new(primary: A, secondary: B) -> Self {
+        TeeRenderer { primary, secondary }
+    }
+
+    /// Get a reference to the primary renderer.
+    #[inline(always)]
+    pub fn primary(&self) -> &A {
+        &self.primary
+    }
+
+    /// Get a reference to the secondary renderer.
+    #[inline(always)]
+    pub fn secondary(&self) -> &B {
+        &self.secondary
+    }
+
+    /// Unwrap the primary and secondary renderers.
+    #[inline(always)]
+    pub fn into_inner(self) -> (A, B) {
+        (self.primary, self.secondary)
+    }
+}
+
+impl<T, A, B> Renderer<T> for TeeRenderer<A, B>
+where
+    T: Signed + PartialOrd + AsPrimitive<usize>,
+    A: Renderer<T>,
+    A::Pixel: Clone,
+    A::Attr: Clone,
+    B: Renderer<T, Pixel = A::Pixel, Attr = A::Attr>,
+{
+    type Pixel = A::Pixel;
+    type Attr = A::Attr;
+    type Error = A::Error;
+
+    fn put_pixel(&mut self, p: Point2<usize>, px: Self::Pixel) {
+        let x = p.0 * self.secondary.width() / self.primary.width();
+        let y = p.1 * self.secondary.height() / self.primary.height();
+        self.secondary.put_pixel((x, y), px.clone());
+        self.primary.put_pixel(p, px);
+    }
+
+    fn get_pixel(&self, p: Point2<usize>) -> Option<Self::Pixel> {
+        self.primary.get_pixel(p)
+    }
+
+    fn clear(&mut self, px: Self::Pixel) {
+        self.secondary.clear(px.clone());
+        self.primary.clear(px);
+    }
+
+    fn swap(&mut self) {
+        self.primary.swap();
+        self.secondary.swap();
+    }
+
+    fn width(&self) -> usize {
+        self.primary.width()
+    }
+
+    fn height(&self) -> usize {
+        self.primary.height()
+    }
+
+    fn get_attr(&self, attr: usize) -> Option<Self::Attr> {
+        self.primary.get_attr(attr)
+    }
+
+    fn set_attr(&mut self, attr: usize, val: Self::Attr) {
+        self.secondary.set_attr(attr, val.clone());
+        self.primary.set_attr(attr, val);
+    }
+
+    fn depth_test(&mut self, p: Point2<usize>, z: T) -> bool {
+        self.primary.depth_test(p, z)
+    }
+
+    fn stencil_test(&self, p: Point2<usize>) -> bool {
+        self.primary.stencil_test(p)
+    }
+
+    fn clamp_attr(&self, attr: Self::Attr) -> Self::Attr {
+        self.primary.clamp_attr(attr)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::iter;
+
+    use renderer::{Renderer, SimpleRenderer};
+    use tri::Triangle;
+    use super::TeeRenderer;
+
+    #[test]
+    fn tee() {
+        let primary = SimpleRenderer::<u8>::new(16, 16);
+        let secondary = SimpleRenderer::<u8>::new(8, 8);
+        let mut renderer = TeeRenderer::new(primary, secondary);
+
+        let triangle = Triangle::with_points([(2.0, 2.0), (14.0, 2.0), (2.0, 14.0)]);
+
+        renderer.set_attr(0, 1);
+        assert!(renderer.draw(iter::once(triangle)).is_ok());
+        renderer.swap();
+
+        let (primary, secondary) = renderer.into_inner();
+        assert_eq!(primary.buffer()[2 * 16 + 2], 1);
+        assert_eq!(primary.buffer()[14 * 16 + 14], 0);
+        assert_eq!(secondary.buffer()[8 + 1], 1);
+        assert_eq!(secondary.buffer()[7 * 8 + 7], 0);
+        assert_eq!(
+            primary.buffer().iter().filter(|&&px| px == 1).count(),
+            triangle.into_iter().count()
+        );
+    }
+}
@@ -0,0 +1,277 @@
+//! Images which can be sampled with texture coordinates, i.e. in `draw_with`.
+
+use num_traits::Float;
+
+use renderer::{color, Pixel};
+use point::Point2;
+
+/// How texture coordinates outside of `[0, 1]` are mapped onto the texture.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum WrapMode {
+    /// Use the nearest texel on the edge of the texture.
+    Clamp,
+    /// Tile the texture infinitely.
+    Repeat,
+}
+
+impl WrapMode {
+    /// Map the texel index `i` into `[0, len)`.
+    #[inline(always)]
+    fn wrap(self, i: i64, len: usize) -> usize {
+        let len = len as i64;
+        match self {
+            WrapMode::Clamp => i.max(0).min(len - 1) as usize,
+            WrapMode::Repeat => i.rem_euclid(len) as usize,
+        }
+    }
+}
+
+/// An image stored row by row, which can be sampled with texture
+/// coordinates, where `(0, 0)` is the top left and `(1, 1)` the bottom right
+/// corner.
+#[derive(Clone, Debug)]
+pub struct Texture<Px> {
+    width: usize,
+    height: usize,
+    pixels: Vec<Px>,
+}
+
+impl<Px> Texture<Px> {
+    /// Create a new `Texture` from `pixels`, stored row by row.
+    ///
+    /// # Panics
+    ///
+    /// If the texture is empty, or `pixels` doesn't hold `width * height`
+    /// pixels.
+    #[inline]
+    pub fn new(width: usize, height: usize, pixels: Vec<Px>) -> Self {
+        assert!(width > 0 && height > 0, "texture is empty");
+        assert_eq!(pixels.len(), width * height, "texture size mismatch");
+
+        Texture {
+            width,
+            height,
+            pixels,
+        }
+    }
+
+    /// Get the width of the texture, in texels.
+    #[inline(always)]
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    /// Get the height of the texture, in texels.
+    #[inline(always)]
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    /// Get a reference to the texels of the texture.
+    #[inline(always)]
+    pub fn pixels(&self) -> &[Px] {
+        &self.pixels
+    }
+
+    /// Get the texel at the index `(x, y)`, wrapped with `wrap`.
+    #[inline(always)]
+    fn texel(&self, x: i64, y: i64, wrap: WrapMode) -> &Px {
+        let x = wrap.wrap(x, self.width);
+        let y = wrap.wrap(y, self.height);
+        &self.pixels[y * self.width + x]
+    }
+}
+
+impl<Px: Clone> Texture<Px> {
+    /// Get the texel containing `uv`, a.k.a. nearest-neighbor sampling.
+    #[inline]
+    pub fn sample<T: Float>(&self, uv: Point2<T>, wrap: WrapMode) -> Px {
+        let x = (uv.0 * T::from(self.width).unwrap()).floor();
+        let y = (uv.1 * T::from(self.height).unwrap()).floor();
+
+        self.texel(x.to_i64().unwrap_or(0), y.to_i64().unwrap_or(0), wrap)
+            .clone()
+    }
+
+    /// Interpolate the 4 texels nearest to `uv`, a.k.a. bilinear sampling.
+    ///
+    /// The centers of the texels are sampled exactly, i.e. the center of
+    /// the top left texel lies at `uv` of half a texel in both directions.
+    #[inline]
+    pub fn sample_bilinear<T: Float>(&self, uv: Point2<T>, wrap: WrapMode) -> Px
+    where
+        Px: Pixel<T>,
+    {
+        let half = T::from(0.5).unwrap();
+        let x = uv.0 * T::from(self.width).unwrap() - half;
+        let y = uv.1 * T::from(self.height).unwrap() - half;
+        let (fx, fy) = (x - x.floor(), y - y.floor());
+        let (x, y) = (x.floor().to_i64().unwrap_or(0), y.floor().to_i64().unwrap_or(0));
+
+        let lerp = |a: Px, b: Px, t: T| Px::lerp3(a.clone(), b, a, [T::one() - t, t, T::zero()]);
+        let top = lerp(
+            self.texel(x, y, wrap).clone(),
+            self.texel(x + 1, y, wrap).clone(),
+            fx,
+        );
+        let bottom = lerp(
+            self.texel(x, y + 1, wrap).clone(),
+            self.texel(x + 1, y + 1, wrap).clone(),
+            fx,
+        );
+
+        lerp(top, bottom, fy)
+    }
+}
+
+/// A texture and its successively halved copies, a.k.a. a mipmap, i.e. to
+/// reduce aliasing when a texture is minified.
+///
+/// The 0'th level is the original texture, every further level is half as
+/// wide and high as the previous one, rounding down but at least 1 texel,
+/// until the last level is 1 by 1 texels.
+#[derive(Clone, Debug)]
+pub struct MipChain<Px> {
+    levels: Vec<Texture<Px>>,
+}
+
+impl MipChain<(u8, u8, u8)> {
+    /// Create a `MipChain` by box-filtering every 2 by 2 block of texels of
+    /// a level into a texel of the next one, starting with `base`.
+    ///
+    /// The texels are averaged in linear space, see `color`, so minified
+    /// textures don't darken.
+    pub fn new(base: Texture<(u8, u8, u8)>) -> Self {
+        let mut levels = vec![base];
+        loop {
+            let next = {
+                let prev = &levels[levels.len() - 1];
+                if prev.width == 1 && prev.height == 1 {
+                    break;
+                }
+
+                let (width, height) = ((prev.width / 2).max(1), (prev.height / 2).max(1));
+                let mut pixels = Vec::with_capacity(width * height);
+                for y in 0..height {
+                    for x in 0..width {
+                        let mut sum = (0.0, 0.0, 0.0);
+                        for &(dx, dy) in &[(0, 0), (1, 0), (0, 1), (1, 1)] {
+                            // a side of 1 texel has no second texel to add
+                            let (sx, sy) = ((2 * x + dx) as i64, (2 * y + dy) as i64);
+                            let px = prev.texel(sx, sy, WrapMode::Clamp);
+                            let (r, g, b) = color::srgb_to_linear(*px);
+                            sum = (sum.0 + r, sum.1 + g, sum.2 + b);
+                        }
+                        let avg = (sum.0 / 4.0, sum.1 / 4.0, sum.2 / 4.0);
+                        pixels.push(color::linear_to_srgb(avg));
+                    }
+                }
+                Texture::new(width, height, pixels)
+            };
+            levels.push(next);
+        }
+
+        MipChain { levels }
+    }
+}
+
+impl<Px> MipChain<Px> {
+    /// Get the levels, starting with the original texture.
+    #[inline(always)]
+    pub fn levels(&self) -> &[Texture<Px>] {
+        &self.levels
+    }
+}
+
+impl<Px: Clone> MipChain<Px> {
+    /// Sample the two levels nearest to the level of detail `lod` with
+    /// `Texture::sample_bilinear` and interpolate between them, a.k.a.
+    /// trilinear sampling.
+    ///
+    /// A `lod` of 0 samples the original texture, every further unit halves
+    /// the resolution, i.e. pick the base 2 logarithm of the count of texels
+    /// per pixel. `lod` is clamped to the available levels.
+    #[inline]
+    pub fn sample_trilinear<T: Float>(&self, uv: Point2<T>, lod: T, wrap: WrapMode) -> Px
+    where
+        Px: Pixel<T>,
+    {
+        let last = self.levels.len() - 1;
+        let lod = lod.max(T::zero()).min(T::from(last).unwrap());
+        let i = lod.floor().to_usize().unwrap_or(0).min(last);
+        let t = lod - T::from(i).unwrap();
+
+        let a = self.levels[i].sample_bilinear(uv, wrap);
+        if i == last || t == T::zero() {
+            return a;
+        }
+        let b = self.levels[i + 1].sample_bilinear(uv, wrap);
+        Px::lerp3(a.clone(), b, a, [T::one() - t, t, T::zero()])
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{MipChain, Texture, WrapMode};
+
+    #[test]
+    fn wrap() {
+        let texture = Texture::new(4, 1, vec![10_u8, 20, 30, 40]);
+        let sample = |u: f64, wrap| texture.sample((u, 0.5), wrap);
+
+        assert_eq!(sample(0.3, WrapMode::Clamp), 20);
+        assert_eq!(sample(0.3, WrapMode::Repeat), 20);
+
+        assert_eq!(sample(1.1, WrapMode::Clamp), 40);
+        assert_eq!(sample(1.1, WrapMode::Repeat), 10);
+        assert_eq!(sample(2.6, WrapMode::Repeat), 30);
+
+        assert_eq!(sample(-0.1, WrapMode::Clamp), 10);
+        assert_eq!(sample(-0.1, WrapMode::Repeat), 40);
+        assert_eq!(sample(-1.6, WrapMode::Repeat), 20);
+    }
+
+    #[test]
+    fn bilinear() {
+        let texture = Texture::new(2, 2, vec![(0, 0, 0), (200, 0, 0), (0, 100, 0), (200, 100, 0)]);
+        let sample = |uv: (f64, f64), wrap| texture.sample_bilinear(uv, wrap);
+
+        // texel centers
+        assert_eq!(sample((0.25, 0.25), WrapMode::Clamp), (0, 0, 0));
+        assert_eq!(sample((0.75, 0.75), WrapMode::Clamp), (200, 100, 0));
+
+        // half a texel off the centers
+        assert_eq!(sample((0.5, 0.25), WrapMode::Clamp), (100, 0, 0));
+        assert_eq!(sample((0.5, 0.5), WrapMode::Clamp), (100, 50, 0));
+
+        // past the edge, clamping keeps the edge while repeating wraps around
+        assert_eq!(sample((1.0, 0.25), WrapMode::Clamp), (200, 0, 0));
+        assert_eq!(sample((1.0, 0.25), WrapMode::Repeat), (100, 0, 0));
+    }
+
+    #[test]
+    fn mip_chain() {
+        let (black, white) = ((0, 0, 0), (255, 255, 255));
+        let mips = MipChain::new(Texture::new(2, 2, vec![black, white, white, black]));
+        let sizes = |mips: &MipChain<_>| {
+            mips.levels()
+                .iter()
+                .map(|level| (level.width(), level.height()))
+                .collect::<Vec<_>>()
+        };
+        assert_eq!(sizes(&mips), [(2, 2), (1, 1)]);
+        // averaged in linear space, the checkerboard is a light gray
+        assert_eq!(mips.levels()[1].pixels(), [(188, 188, 188)]);
+
+        let wide = MipChain::new(Texture::new(5, 2, vec![white; 10]));
+        assert_eq!(sizes(&wide), [(5, 2), (2, 1), (1, 1)]);
+        assert!(wide.levels().iter().all(|level| level.pixels().iter().all(|&px| px == white)));
+
+        let uv = (0.25, 0.25);
+        assert_eq!(mips.sample_trilinear(uv, 0.0, WrapMode::Clamp), black);
+        assert_eq!(mips.sample_trilinear(uv, 1.0, WrapMode::Clamp), (188, 188, 188));
+        assert_eq!(mips.sample_trilinear(uv, 5.0, WrapMode::Clamp), (188, 188, 188));
+        assert_eq!(mips.sample_trilinear(uv, -1.0, WrapMode::Clamp), black);
+        assert_eq!(mips.sample_trilinear(uv, 0.5, WrapMode::Clamp), (94, 94, 94));
+    }
+}
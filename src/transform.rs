@@ -0,0 +1,247 @@
+//! 2D affine transforms of primitives, and a transform stack for drawing
+//! them.
+
+use std::iter;
+
+use num_traits::{AsPrimitive, Float, Signed};
+
+use renderer::{Coord, Drawable, Renderer, Stats};
+use point::Point2;
+
+/// A 2D affine transform, i.e. a 2×3 matrix in row-major order mapping
+/// `(x, y)` to `(m[0][0] x + m[0][1] y + m[0][2], m[1][0] x + m[1][1] y + m[1][2])`.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Affine2<T> {
+    m: [[T; 3]; 2],
+}
+
+impl<T: Float> Affine2<T> {
+    #[inline(always)]
+    pub fn new(m: [[T; 3]; 2]) -> Self {
+        Affine2 { m }
+    }
+
+    /// The transform mapping every point to itself.
+    #[inline]
+    pub fn identity() -> Self {
+        Affine2::scale(T::one(), T::one())
+    }
+
+    /// Move points by `(dx, dy)`.
+    #[inline]
+    pub fn translate(dx: T, dy: T) -> Self {
+        let (zero, one) = (T::zero(), T::one());
+        Affine2::new([[one, zero, dx], [zero, one, dy]])
+    }
+
+    /// Scale points by `sx` along x and `sy` along y, around the origin.
+    #[inline]
+    pub fn scale(sx: T, sy: T) -> Self {
+        let zero = T::zero();
+        Affine2::new([[sx, zero, zero], [zero, sy, zero]])
+    }
+
+    /// Rotate points by `angle` radians around the origin, from the x axis
+    /// towards the y axis.
+    #[inline]
+    pub fn rotate(angle: T) -> Self {
+        let (sin, cos) = angle.sin_cos();
+        let zero = T::zero();
+        Affine2::new([[cos, -sin, zero], [sin, cos, zero]])
+    }
+
+    /// Get the transform which applies `other` first and `self` second.
+    #[inline]
+    pub fn compose(&self, other: &Affine2<T>) -> Affine2<T> {
+        let (a, b) = (self.m, other.m);
+        let row = |r: [T; 3]| {
+            [
+                r[0] * b[0][0] + r[1] * b[1][0],
+                r[0] * b[0][1] + r[1] * b[1][1],
+                r[0] * b[0][2] + r[1] * b[1][2] + r[2],
+            ]
+        };
+
+        Affine2::new([row(a[0]), row(a[1])])
+    }
+
+    /// Get the determinant of the linear part, i.e. the factor by which
+    /// areas are scaled.
+    #[inline]
+    pub fn det(&self) -> T {
+        self.m[0][0] * self.m[1][1] - self.m[0][1] * self.m[1][0]
+    }
+
+    /// Transform the point `p`.
+    #[inline]
+    pub fn apply(&self, (x, y): Point2<T>) -> Point2<T> {
+        let m = self.m;
+        (
+            m[0][0] * x + m[0][1] * y + m[0][2],
+            m[1][0] * x + m[1][1] * y + m[1][2],
+        )
+    }
+}
+
+/// A trait for primitive types, which can be transformed by an `Affine2`.
+pub trait Transform<T> {
+    /// Get `self` transformed by `m`.
+    fn transform(&self, m: &Affine2<T>) -> Self;
+}
+
+impl<T: Float> Transform<T> for Point2<T> {
+    #[inline(always)]
+    fn transform(&self, m: &Affine2<T>) -> Self {
+        m.apply(*self)
+    }
+}
+
+/// An immediate-mode drawing API over a `Renderer` with a stack of
+/// transforms, i.e. for hierarchical drawing like a wheel on a car on a
+/// road.
+///
+/// Every primitive is transformed by the top of the stack before it's
+/// drawn. The stack starts out with the identity transform, which can't be
+/// popped.
+#[derive(Debug)]
+pub struct SceneBuilder<'a, T, R: 'a> {
+    renderer: &'a mut R,
+    stack: Vec<Affine2<T>>,
+}
+
+impl<'a, T: Float, R: 'a> SceneBuilder<'a, T, R> {
+    /// Create a `SceneBuilder` drawing with `renderer`.
+    #[inline]
+    pub fn new(renderer: &'a mut R) -> Self {
+        SceneBuilder {
+            renderer,
+            stack: vec![Affine2::identity()],
+        }
+    }
+
+    /// Get the transform primitives are drawn with.
+    #[inline]
+    pub fn transform(&self) -> &Affine2<T> {
+        // the identity transform at the bottom is never popped
+        &self.stack[self.stack.len() - 1]
+    }
+
+    /// Save the current transform, so that it can be restored by `pop`.
+    #[inline]
+    pub fn push(&mut self) {
+        let top = *self.transform();
+        self.stack.push(top);
+    }
+
+    /// Restore the transform saved by the matching `push`. Popping more
+    /// often than pushing does nothing.
+    #[inline]
+    pub fn pop(&mut self) {
+        if self.stack.len() > 1 {
+            self.stack.pop();
+        }
+    }
+
+    /// Multiply the current transform by `m`, so that `m` is applied to
+    /// primitives first, i.e. in the local space of the current transform.
+    #[inline]
+    pub fn mul(&mut self, m: &Affine2<T>) {
+        let top = self.transform().compose(m);
+        let last = self.stack.len() - 1;
+        self.stack[last] = top;
+    }
+
+    /// Get a mutable reference to the renderer, i.e. to set attributes.
+    #[inline(always)]
+    pub fn renderer(&mut self) -> &mut R {
+        self.renderer
+    }
+}
+
+impl<'a, T, R> SceneBuilder<'a, T, R>
+where
+    T: Float + Signed + AsPrimitive<usize>,
+    R: Renderer<T> + 'a,
+{
+    /// Draw `shape` transformed by the current transform.
+    ///
+    /// # Returns
+    ///
+    /// Same as `Renderer::draw`.
+    #[inline]
+    pub fn draw<C, S>(&mut self, shape: S) -> Result<Stats, R::Error>
+    where
+        C: Coord<T>,
+        S: Transform<T> + Drawable<T, C>,
+    {
+        let shape = shape.transform(self.transform());
+        self.renderer.draw(iter::once(shape))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::f64::consts::FRAC_PI_2;
+
+    use tri::Triangle;
+    use super::{Affine2, SceneBuilder, Transform};
+
+    #[test]
+    fn compose() {
+        let m = Affine2::translate(1.0, 2.0).compose(&Affine2::scale(2.0, 3.0));
+        assert_eq!(m.apply((1.0, 1.0)), (3.0, 5.0));
+        assert_eq!(Affine2::identity().compose(&m), m);
+    }
+
+    #[test]
+    fn rotate_triangle() {
+        let triangle = Triangle::with_points([(1.0, 0.0), (3.0, 0.0), (1.0, 2.0)]);
+        let rotated = triangle.transform(&Affine2::rotate(FRAC_PI_2));
+        let expected = [(0.0, 1.0), (0.0, 3.0), (-2.0, 1.0)];
+
+        for (p, e) in rotated.points().iter().zip(&expected) {
+            assert!((p.0 - e.0).abs() < 1e-9);
+            assert!((p.1 - e.1).abs() < 1e-9);
+        }
+        assert!((rotated.det() - triangle.det()).abs() < 1e-9);
+    }
+
+    #[test]
+    fn scene_builder() {
+        use rect::Rectangle;
+        use renderer::{Renderer, SimpleRenderer};
+
+        let mut renderer = SimpleRenderer::single_buffered(8, 8);
+        renderer.set_attr(0, 1_u8);
+        let square = Rectangle::new(0.0, 1.0, 0.0, 1.0);
+
+        {
+            let mut scene = SceneBuilder::new(&mut renderer);
+            scene.pop();
+            assert_eq!(*scene.transform(), Affine2::identity());
+
+            scene.push();
+            scene.mul(&Affine2::translate(2.0, 3.0));
+            scene.mul(&Affine2::scale(3.0, 2.0));
+            let stats = scene.draw(square).unwrap();
+            assert_eq!(stats.fragments, 6);
+
+            scene.pop();
+            assert_eq!(*scene.transform(), Affine2::identity());
+            scene.renderer().set_attr(0, 2);
+            assert!(scene.draw(square).is_ok());
+        }
+
+        let lit = |value| {
+            renderer
+                .buffer()
+                .iter()
+                .enumerate()
+                .filter(|&(_, &px)| px == value)
+                .map(|(i, _)| (i % 8, i / 8))
+                .collect::<Vec<_>>()
+        };
+        assert_eq!(lit(1), [(2, 3), (3, 3), (4, 3), (2, 4), (3, 4), (4, 4)]);
+        assert_eq!(lit(2), [(0, 0)]);
+    }
+}
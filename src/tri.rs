@@ -1,8 +1,13 @@
+use std::array;
+
+use line_drawing::FloatNum;
 use num_traits::{AsPrimitive, Float};
 
 use renderer::{Coord, Drawable};
 use point::Point2;
+use line::Line;
 use rect::{self, Rectangle};
+use transform::{Affine2, Transform};
 
 pub type Coordinate<T> = (Point2<T>, [T; 3]);
 
@@ -18,7 +23,18 @@ impl<T: Copy> Coord<T> for Coordinate<T> {
     }
 }
 
-#[derive(Clone, Copy, Debug)]
+/// The order of the vertices of a triangle, in a y-up coordinate system.
+/// On a screen with y pointing down, the orders appear mirrored.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Winding {
+    /// Clockwise, i.e. a negative `det`.
+    Cw,
+    /// Counter-clockwise, i.e. a positive `det`.
+    Ccw,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct Triangle<T> {
     points: [Point2<T>; 3],
 }
@@ -31,6 +47,15 @@ where
     fn vertices(&self) -> usize {
         3
     }
+
+    /// Iterate over the triangle like `into_iter_clipped`, with the
+    /// viewport as the clip rectangle.
+    #[inline]
+    fn into_iter_viewport(self, width: usize, height: usize) -> IntoIter<T> {
+        let size = |n: usize| T::from(n).unwrap_or_else(T::infinity);
+        let viewport = Rectangle::new(T::zero(), size(width), T::zero(), size(height));
+        self.into_iter_clipped(&viewport)
+    }
 }
 
 impl<T: Float + AsPrimitive<i64>> Triangle<T> {
@@ -39,6 +64,12 @@ impl<T: Float + AsPrimitive<i64>> Triangle<T> {
         Triangle { points }
     }
 
+    /// Get the vertices of the triangle.
+    #[inline(always)]
+    pub fn points(&self) -> &[Point2<T>; 3] {
+        &self.points
+    }
+
     #[inline]
     pub fn det(&self) -> T {
         let (x1, y1) = self.points[0];
@@ -47,6 +78,154 @@ impl<T: Float + AsPrimitive<i64>> Triangle<T> {
 
         (y2 - y3) * (x1 - x3) + (x3 - x2) * (y1 - y3)
     }
+
+    /// Whether the vertices of the triangle are ordered in the `front`
+    /// winding, i.e. for backface culling.
+    ///
+    /// Triangles which are degenerate, or so thin that the sign of `det` is
+    /// dominated by rounding errors, are never front facing.
+    #[inline]
+    pub fn is_front_facing(&self, front: Winding) -> bool {
+        let p = self.points;
+        let len2 = |a: Point2<T>, b: Point2<T>| (b.0 - a.0).powi(2) + (b.1 - a.1).powi(2);
+        let longest = len2(p[0], p[1]).max(len2(p[1], p[2])).max(len2(p[2], p[0]));
+        let det = self.det();
+
+        if det.abs() <= longest * T::epsilon() * T::from(4.0).unwrap() {
+            return false;
+        }
+
+        match front {
+            Winding::Cw => det < T::zero(),
+            Winding::Ccw => det > T::zero(),
+        }
+    }
+
+    /// Get the area of the triangle.
+    #[inline]
+    pub fn area(&self) -> T {
+        self.det().abs() / T::from(2.0).unwrap()
+    }
+
+    /// Whether `p` lies inside or on an edge of the triangle. Unlike the
+    /// rasterizer, `p` needn't lie on the pixel grid.
+    ///
+    /// Degenerate triangles with zero area contain no points.
+    #[inline]
+    pub fn contains(&self, p: Point2<T>) -> bool {
+        let det = self.det();
+        det != T::zero() && inside(weights(&self.points, det, p))
+    }
+
+    /// Get the barycentric coordinate of `p`, i.e. the weights of the
+    /// vertices, like the rasterizer yields them. Unlike the rasterizer,
+    /// `p` needn't lie on the pixel grid or inside of the triangle, in
+    /// which case some weights are negative.
+    ///
+    /// # Returns
+    ///
+    /// - `None` if the triangle is degenerate with zero area.
+    /// - `Some([p1, p2, p3])` otherwise, where the weights sum up to 1.
+    #[inline]
+    pub fn barycentric_at(&self, p: Point2<T>) -> Option<[T; 3]> {
+        let det = self.det();
+        if det == T::zero() {
+            None
+        } else {
+            Some(weights(&self.points, det, p))
+        }
+    }
+
+    /// Get the smallest `Rectangle` containing the triangle.
+    #[inline]
+    pub fn bounding_box(&self) -> Rectangle<T> {
+        let points = self.points;
+        let min_x = points[0].0.min(points[1].0).min(points[2].0);
+        let max_x = points[0].0.max(points[1].0).max(points[2].0);
+        let min_y = points[0].1.min(points[1].1).min(points[2].1);
+        let max_y = points[0].1.max(points[1].1).max(points[2].1);
+
+        Rectangle::new(min_x, max_x, min_y, max_y)
+    }
+
+    /// Clip the triangle to the closed bounds of `rect`, a.k.a.
+    /// Sutherland-Hodgman, i.e. so that large triangles reaching far
+    /// off-screen aren't rasterized over their whole bounding box.
+    ///
+    /// The clipped polygon is convex and split into a fan of triangles,
+    /// which keep the winding of `self`.
+    ///
+    /// # Returns
+    ///
+    /// - `self` unchanged if it lies entirely within `rect`.
+    /// - An empty `Vec` if it lies entirely outside of `rect`, or is
+    ///   degenerate.
+    /// - Up to 5 triangles covering the part within `rect` otherwise.
+    pub fn clip_to_rect(&self, rect: &Rectangle<T>) -> Vec<Triangle<T>> {
+        let ((x0, y0), (x1, y1)) = rect.min_max();
+        let within = |&(x, y): &Point2<T>| x >= x0 && x <= x1 && y >= y0 && y <= y1;
+        if self.points.iter().all(within) {
+            return vec![*self];
+        }
+
+        // the signed distance along one axis, nonnegative inside
+        let planes: [(bool, T, T); 4] = [
+            (true, x0, T::one()),
+            (true, x1, -T::one()),
+            (false, y0, T::one()),
+            (false, y1, -T::one()),
+        ];
+
+        let mut polygon = self.points.to_vec();
+        for &(vertical, bound, sign) in &planes {
+            let dist = |p: Point2<T>| (if vertical { p.0 } else { p.1 } - bound) * sign;
+            let input = polygon;
+            polygon = Vec::with_capacity(input.len() + 1);
+
+            for (i, &a) in input.iter().enumerate() {
+                let b = input[(i + 1) % input.len()];
+                let (da, db) = (dist(a), dist(b));
+                if da >= T::zero() {
+                    polygon.push(a);
+                }
+                if (da >= T::zero()) != (db >= T::zero()) {
+                    let t = da / (da - db);
+                    polygon.push(if vertical {
+                        (bound, a.1 + (b.1 - a.1) * t)
+                    } else {
+                        (a.0 + (b.0 - a.0) * t, bound)
+                    });
+                }
+            }
+        }
+
+        (1..polygon.len().saturating_sub(1))
+            .map(|i| Triangle::with_points([polygon[0], polygon[i], polygon[i + 1]]))
+            .filter(|tri| tri.det() != T::zero())
+            .collect()
+    }
+}
+
+impl<T: FloatNum> Triangle<T> {
+    /// Get the outline of the triangle, i.e. for drawing it as a wireframe.
+    /// The i'th edge runs from the i'th vertex to the next one.
+    #[inline]
+    pub fn edges(&self) -> [Line<T>; 3] {
+        let p = self.points;
+        [
+            Line::new(p[0], p[1]),
+            Line::new(p[1], p[2]),
+            Line::new(p[2], p[0]),
+        ]
+    }
+}
+
+impl<T: Float + AsPrimitive<i64>> Transform<T> for Triangle<T> {
+    #[inline]
+    fn transform(&self, m: &Affine2<T>) -> Self {
+        let p = self.points;
+        Triangle::with_points([m.apply(p[0]), m.apply(p[1]), m.apply(p[2])])
+    }
 }
 
 impl<T: Float + AsPrimitive<i64> + Copy + 'static> IntoIterator for Triangle<T>
@@ -58,23 +237,333 @@ where
 
     #[inline]
     fn into_iter(self) -> Self::IntoIter {
+        self.iter_within(self.bounding_box())
+    }
+}
+
+impl<T: Float + AsPrimitive<i64> + Copy + 'static> Triangle<T>
+where
+    i64: AsPrimitive<T>,
+{
+    /// Iterate over the pixels of the triangle within `rect`, i.e. its
+    /// bounding box or a part of it.
+    #[inline]
+    fn iter_within(self, rect: Rectangle<T>) -> IntoIter<T> {
         let det = self.det();
         let points = self.points;
+        // degenerate triangles would divide by zero, so draw nothing
+        let rect = if det == T::zero() {
+            Rectangle::new(T::zero(), T::zero(), T::zero(), T::zero())
+        } else {
+            rect
+        };
+        let rect = rect.into_iter();
+
+        // flip the edge functions of clockwise triangles, so that they're
+        // nonnegative inside of every triangle
+        let sign = det.signum();
+        let (x1, y1) = points[0];
+        let (x2, y2) = points[1];
+        let (x3, y3) = points[2];
+
+        IntoIter {
+            det,
+            det_recip: det.abs().recip(),
+            edges: [
+                (y2 - y3) * sign,
+                (x3 - x2) * sign,
+                (y3 - y1) * sign,
+                (x1 - x3) * sign,
+            ],
+            cursor: ((T::nan(), T::nan()), [T::zero(); 3]),
+            inclusive: [true; 3],
+            rect,
+            points,
+            fallback: None,
+        }
+    }
+
+    /// Iterate over the triangle like `into_iter`, but guarantee at least
+    /// one fragment for triangles with a nonzero area.
+    ///
+    /// Triangles smaller than a pixel may not contain any integer point and
+    /// would vanish. In that case, the pixel containing the centroid is
+    /// emitted with the barycentric coordinate of the centroid.
+    #[inline]
+    pub fn into_iter_nonempty(self) -> IntoIter<T> {
+        let three = T::from(3.0).unwrap();
+        let x = (self.points[0].0 + self.points[1].0 + self.points[2].0) / three;
+        let y = (self.points[0].1 + self.points[1].1 + self.points[2].1) / three;
+        let third = three.recip();
+        let fallback = if self.det() != T::zero() {
+            Some(((x.floor(), y.floor()), [third; 3]))
+        } else {
+            None
+        };
+
+        IntoIter {
+            fallback,
+            ..self.into_iter()
+        }
+    }
+
+    /// Iterate over the triangle like `into_iter`, but only visit pixels
+    /// within `clip`, i.e. the viewport.
+    ///
+    /// The bounding box of the triangle is intersected with `clip` before
+    /// iterating, so large triangles which are mostly off-screen don't waste
+    /// time on pixels which would be discarded anyway.
+    #[inline]
+    pub fn into_iter_clipped(self, clip: &Rectangle<T>) -> IntoIter<T> {
+        self.iter_within(self.bounding_box().clamp_to(clip))
+    }
+
+    /// Iterate over the triangle like `into_iter`, but apply the top-left
+    /// fill rule, so that triangles sharing an edge tile without gaps or
+    /// pixels covered by both.
+    ///
+    /// A pixel exactly on an edge is only yielded if that edge is a top
+    /// edge, i.e. horizontal with the triangle below it on screen, or a left
+    /// edge, i.e. with the triangle to the right of it. Unlike `into_iter`,
+    /// every pixel of the triangle is visited, even if the bounding box
+    /// doesn't lie on the pixel grid.
+    #[inline]
+    pub fn into_iter_watertight(self) -> IntoIter<T> {
+        let mut iter = self.into_iter();
+        if iter.det == T::zero() {
+            return iter;
+        }
+
+        // the gradients of the edge functions point into the triangle
+        let e = iter.edges;
+        let gradients = [(e[0], e[1]), (e[2], e[3]), (-(e[0] + e[2]), -(e[1] + e[3]))];
+        for (inclusive, &(dx, dy)) in iter.inclusive.iter_mut().zip(&gradients) {
+            *inclusive = dx > T::zero() || (dx == T::zero() && dy > T::zero());
+        }
+
+        let points = self.points;
+        let min_x = points[0].0.min(points[1].0).min(points[2].0);
+        let max_x = points[0].0.max(points[1].0).max(points[2].0);
+        let min_y = points[0].1.min(points[1].1).min(points[2].1);
+        let max_y = points[0].1.max(points[1].1).max(points[2].1);
+        iter.rect = Rectangle::new(
+            min_x.ceil(),
+            max_x.floor() + T::one(),
+            min_y.ceil(),
+            max_y.floor() + T::one(),
+        )
+        .into_iter();
+
+        iter
+    }
+
+    /// Iterate over the triangle with anti-aliased edges, additionally
+    /// yielding the coverage of each pixel in `(0, 1]`.
+    ///
+    /// The coverage is estimated by testing 4 subsamples in each pixel.
+    /// Pixels partially covered by the triangle are yielded even if their
+    /// center lies outside of it, so their barycentric weights may be
+    /// slightly outside of `[0, 1]`. Multiply the coverage into the output
+    /// color for smooth edges.
+    #[inline]
+    pub fn into_iter_aa(self) -> AaIter<T> {
+        let det = self.det();
+        let points = self.points;
+        let half = T::from(0.5).unwrap();
         let min_x = points[0].0.min(points[1].0).min(points[2].0);
         let max_x = points[0].0.max(points[1].0).max(points[2].0);
         let min_y = points[0].1.min(points[1].1).min(points[2].1);
         let max_y = points[0].1.max(points[1].1).max(points[2].1);
-        let rect = Rectangle::new(min_x, max_x, min_y, max_y).into_iter();
+        let rect = if det == T::zero() {
+            Rectangle::new(T::zero(), T::zero(), T::zero(), T::zero())
+        } else {
+            Rectangle::new(
+                (min_x - half).floor(),
+                (max_x + half).floor() + T::one(),
+                (min_y - half).floor(),
+                (max_y + half).floor() + T::one(),
+            )
+        };
+
+        AaIter {
+            det,
+            rect: rect.into_iter(),
+            points,
+        }
+    }
+
+    /// Iterate over the triangle like `into_iter`, but additionally tag
+    /// every fragment with the edges it lies on, i.e. to draw the outline
+    /// and the fill in one pass.
+    ///
+    /// A fragment lies on an edge if the weight of the vertex opposite of
+    /// it is at most `tol`. The edges are numbered like `edges`, so
+    /// fragments near a vertex lie on both edges meeting there.
+    #[inline]
+    pub fn into_iter_edges(self, tol: T) -> EdgeIter<T> {
+        EdgeIter {
+            inner: self.into_iter(),
+            tol,
+        }
+    }
+
+    /// Iterate over the triangle like `into_iter`, but yield the vertex
+    /// attributes `attrs`, i.e. colors, normals or texture coordinates,
+    /// interpolated at every fragment instead of the barycentric weights.
+    #[inline]
+    pub fn into_iter_interp<A: Lerp3<T>>(self, attrs: [A; 3]) -> InterpTri<T, A> {
+        InterpTri {
+            inner: self.into_iter(),
+            attrs,
+        }
+    }
+}
+
+/// A trait for vertex attributes, which can be interpolated across a
+/// triangle.
+///
+/// Unlike `Pixel`, the values are neither rounded nor clamped, so it's
+/// meant for the inputs of shading rather than its output.
+///
+/// # Parameters
+///
+/// - `T` represents the primitive numeric type of the weights.
+pub trait Lerp3<T>: Sized {
+    /// Interpolate `a`, `b` and `c` with the barycentric weights `w`.
+    fn lerp3(a: &Self, b: &Self, c: &Self, w: [T; 3]) -> Self;
+}
+
+impl Lerp3<f32> for f32 {
+    #[inline(always)]
+    fn lerp3(a: &Self, b: &Self, c: &Self, w: [f32; 3]) -> Self {
+        w[0] * a + w[1] * b + w[2] * c
+    }
+}
+
+impl Lerp3<f64> for f64 {
+    #[inline(always)]
+    fn lerp3(a: &Self, b: &Self, c: &Self, w: [f64; 3]) -> Self {
+        w[0] * a + w[1] * b + w[2] * c
+    }
+}
+
+impl<T: Float> Lerp3<T> for (T, T, T) {
+    #[inline]
+    fn lerp3(a: &Self, b: &Self, c: &Self, w: [T; 3]) -> Self {
+        let f = |a: T, b: T, c: T| w[0] * a + w[1] * b + w[2] * c;
+        (f(a.0, b.0, c.0), f(a.1, b.1, c.1), f(a.2, b.2, c.2))
+    }
+}
+
+/// Every element is interpolated separately.
+impl<T: Copy, A: Lerp3<T>, const N: usize> Lerp3<T> for [A; N] {
+    #[inline]
+    fn lerp3(a: &Self, b: &Self, c: &Self, w: [T; 3]) -> Self {
+        array::from_fn(|i| A::lerp3(&a[i], &b[i], &c[i], w))
+    }
+}
+
+/// A set of the edges of a triangle, numbered like `Triangle::edges`.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub struct EdgeMask(pub u8);
+
+impl EdgeMask {
+    /// Whether the set contains the `edge`'th edge.
+    #[inline(always)]
+    pub fn contains(self, edge: usize) -> bool {
+        edge < 3 && self.0 & (1 << edge) != 0
+    }
 
-        IntoIter { det, rect, points }
+    /// Get the count of edges in the set.
+    #[inline(always)]
+    pub fn count(self) -> usize {
+        self.0.count_ones() as usize
     }
+
+    /// Whether the set contains no edges, i.e. for interior fragments.
+    #[inline(always)]
+    pub fn is_empty(self) -> bool {
+        self.0 == 0
+    }
+}
+
+/// Compute the barycentric coordinate of `(x, y)`.
+#[inline(always)]
+fn weights<T: Float>(points: &[Point2<T>; 3], det: T, (x, y): Point2<T>) -> [T; 3] {
+    let (x1, y1) = points[0];
+    let (x2, y2) = points[1];
+    let (x3, y3) = points[2];
+    let p1 = ((y2 - y3) * (x - x3) + (x3 - x2) * (y - y3)) / det;
+    let p2 = ((y3 - y1) * (x - x3) + (x1 - x3) * (y - y3)) / det;
+
+    [p1, p2, T::one() - p1 - p2]
+}
+
+/// Whether the barycentric coordinate `p` lies inside of the triangle.
+#[inline(always)]
+fn inside<T: Float>(p: [T; 3]) -> bool {
+    p[0] >= T::zero() && p[1] >= T::zero() && p[0] + p[1] <= T::one()
 }
 
 #[derive(Debug)]
 pub struct IntoIter<T> {
     det: T,
+    /// The reciprocal of the absolute of `det`.
+    det_recip: T,
+    /// The coefficients of the first two edge functions along x and y,
+    /// relative to the third vertex. See `edge_values`.
+    edges: [T; 4],
+    /// The next point in the current row and its edge functions.
+    cursor: (Point2<T>, [T; 3]),
+    /// Whether points exactly on each edge are inside of the triangle.
+    inclusive: [bool; 3],
     rect: rect::IntoIter<T>,
     points: [Point2<T>; 3],
+    fallback: Option<Coordinate<T>>,
+}
+
+impl<T: Float> IntoIter<T> {
+    /// Get the screenspace derivative of a vertex attribute along x.
+    ///
+    /// Barycentric weights change linearly across the triangle, so the
+    /// derivative is constant for every fragment of the triangle.
+    ///
+    /// # Parameters
+    ///
+    /// - `attr` the value of the attribute at each of the three vertices.
+    #[inline]
+    pub fn ddx(&self, attr: [T; 3]) -> T {
+        let (_, y1) = self.points[0];
+        let (_, y2) = self.points[1];
+        let (_, y3) = self.points[2];
+
+        ((attr[0] - attr[2]) * (y2 - y3) + (attr[1] - attr[2]) * (y3 - y1)) / self.det
+    }
+
+    /// Get the screenspace derivative of a vertex attribute along y.
+    ///
+    /// See `ddx`.
+    #[inline]
+    pub fn ddy(&self, attr: [T; 3]) -> T {
+        let (x1, _) = self.points[0];
+        let (x2, _) = self.points[1];
+        let (x3, _) = self.points[2];
+
+        ((attr[0] - attr[2]) * (x3 - x2) + (attr[1] - attr[2]) * (x1 - x3)) / self.det
+    }
+
+    /// Compute the edge functions at `(x, y)`, i.e. the barycentric
+    /// coordinate of `(x, y)` before dividing by the absolute of `det`.
+    #[inline(always)]
+    fn edge_values(&self, (x, y): Point2<T>) -> [T; 3] {
+        let e = self.edges;
+        let (x3, y3) = self.points[2];
+        let (dx, dy) = (x - x3, y - y3);
+        let n1 = e[0] * dx + e[1] * dy;
+        let n2 = e[2] * dx + e[3] * dy;
+
+        [n1, n2, self.det.abs() - n1 - n2]
+    }
 }
 
 impl<T: Float + Copy + 'static> Iterator for IntoIter<T>
@@ -85,31 +574,161 @@ where
 
     #[inline]
     fn next(&mut self) -> Option<Self::Item> {
+        let e = self.edges;
+        let step = [e[0], e[2], -(e[0] + e[2])];
+
         loop {
-            match self.rect.next() {
-                None => break None,
-                Some((x, y)) => {
-                    let (x1, y1) = self.points[0];
-                    let (x2, y2) = self.points[1];
-                    let (x3, y3) = self.points[2];
-                    let p1 = ((y2 - y3) * (x - x3) + (x3 - x2) * (y - y3)) / self.det;
-                    let p2 = ((y3 - y1) * (x - x3) + (x1 - x3) * (y - y3)) / self.det;
-
-                    if p1 >= T::zero() && p2 >= T::zero() && p1 + p2 <= T::one() {
-                        let p3 = T::one() - p1 - p2;
-                        break Some(((x, y), [p1, p2, p3]));
-                    }
-                }
+            let (x, y) = match self.rect.next() {
+                None => break self.fallback.take(),
+                Some(p) => p,
+            };
+
+            // step the edge functions along the row, and evaluate them from
+            // scratch at the start of every row, so rounding errors don't
+            // accumulate across rows
+            let n = if self.cursor.0 == (x, y) {
+                self.cursor.1
+            } else {
+                self.edge_values((x, y))
+            };
+            self.cursor = (
+                (x + T::one(), y),
+                [n[0] + step[0], n[1] + step[1], n[2] + step[2]],
+            );
+
+            // the inside test is done before dividing by `det`, so it's exact
+            // for points and vertices on the pixel grid
+            let inside = |n: T, inclusive| n > T::zero() || (inclusive && n == T::zero());
+            let c = self.inclusive;
+            if inside(n[0], c[0]) && inside(n[1], c[1]) && inside(n[2], c[2]) {
+                self.fallback = None;
+                let p1 = n[0] * self.det_recip;
+                let p2 = n[1] * self.det_recip;
+                break Some(((x, y), [p1, p2, T::one() - p1 - p2]));
+            }
+        }
+    }
+
+    /// The upper bound is the remaining area of the bounding box.
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let rect = self.rect.size_hint().1;
+        let fallback = usize::from(self.fallback.is_some());
+        (0, rect.and_then(|n| n.checked_add(fallback)))
+    }
+}
+
+/// An iterator over the pixels of a triangle and their coverage.
+///
+/// See `Triangle::into_iter_aa`.
+#[derive(Debug)]
+pub struct AaIter<T> {
+    det: T,
+    rect: rect::IntoIter<T>,
+    points: [Point2<T>; 3],
+}
+
+impl<T: Float + Copy + 'static> Iterator for AaIter<T>
+where
+    i64: AsPrimitive<T>,
+{
+    type Item = (Coordinate<T>, T);
+
+    #[inline]
+    fn next(&mut self) -> Option<Self::Item> {
+        let quarter = T::from(0.25).unwrap();
+        let offsets = [
+            (-quarter, -quarter),
+            (quarter, -quarter),
+            (-quarter, quarter),
+            (quarter, quarter),
+        ];
+
+        loop {
+            let (x, y) = self.rect.next()?;
+            let covered = offsets
+                .iter()
+                .filter(|&&(dx, dy)| inside(weights(&self.points, self.det, (x + dx, y + dy))))
+                .count();
+
+            if covered > 0 {
+                let coverage = T::from(covered).unwrap() * quarter;
+                let p = weights(&self.points, self.det, (x, y));
+                break Some((((x, y), p), coverage));
             }
         }
     }
 }
 
+/// An iterator over the pixels of a triangle and the edges they lie on.
+///
+/// See `Triangle::into_iter_edges`.
+#[derive(Debug)]
+pub struct EdgeIter<T> {
+    inner: IntoIter<T>,
+    tol: T,
+}
+
+impl<T: Float + Copy + 'static> Iterator for EdgeIter<T>
+where
+    i64: AsPrimitive<T>,
+{
+    type Item = (Point2<T>, [T; 3], EdgeMask);
+
+    #[inline]
+    fn next(&mut self) -> Option<Self::Item> {
+        let tol = self.tol;
+        self.inner.next().map(|(p, w)| {
+            // the i'th edge lies opposite of the vertex after its end
+            let mask = (0..3)
+                .filter(|&edge| w[(edge + 2) % 3] <= tol)
+                .fold(0, |mask, edge| mask | 1 << edge);
+            (p, w, EdgeMask(mask))
+        })
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.inner.size_hint()
+    }
+}
+
+/// An iterator over the pixels of a triangle and the vertex attributes
+/// interpolated at them.
+///
+/// See `Triangle::into_iter_interp`.
+#[derive(Debug)]
+pub struct InterpTri<T, A> {
+    inner: IntoIter<T>,
+    attrs: [A; 3],
+}
+
+impl<T: Float + Copy + 'static, A: Lerp3<T>> Iterator for InterpTri<T, A>
+where
+    i64: AsPrimitive<T>,
+{
+    type Item = (Point2<T>, A);
+
+    #[inline]
+    fn next(&mut self) -> Option<Self::Item> {
+        let [ref a, ref b, ref c] = self.attrs;
+        self.inner.next().map(|(p, w)| (p, A::lerp3(a, b, c, w)))
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.inner.size_hint()
+    }
+}
+
 #[cfg(test)]
 mod tests {
+    #[cfg(feature = "image")]
     use rand;
+    #[cfg(feature = "image")]
     use image::{ImageBuffer, Pixel, Rgb};
 
+    use rect::Rectangle;
     use super::Triangle;
 
     #[test]
@@ -140,6 +759,351 @@ mod tests {
     }
 
     #[test]
+    fn eq() {
+        let points = [(0.0, 0.0), (3.0, 0.0), (0.0, 3.0)];
+        assert_eq!(Triangle::with_points(points), Triangle::with_points(points));
+        assert_ne!(
+            Triangle::with_points(points),
+            Triangle::with_points([(0.0, 0.0), (3.0, 0.0), (0.0, 4.0)])
+        );
+    }
+
+    #[test]
+    fn contains() {
+        let triangle = Triangle::with_points([(0.0, 0.0), (3.0, 0.0), (0.0, 3.0)]);
+        assert_eq!(triangle.area(), 4.5);
+        assert!(triangle.contains((0.5, 0.75)));
+        assert!(triangle.contains((1.5, 1.5)));
+        assert!(!triangle.contains((2.0, 1.25)));
+        assert!(!triangle.contains((-0.1, 1.0)));
+
+        let line = Triangle::with_points([(0.0, 0.0), (1.0, 1.0), (2.0, 2.0)]);
+        assert_eq!(line.area(), 0.0);
+        assert!(!line.contains((1.0, 1.0)));
+    }
+
+    #[test]
+    fn barycentric_at() {
+        let points = [(1.0_f64, 1.0), (7.0, 2.0), (2.0, 6.0)];
+        let triangle = Triangle::with_points(points);
+        assert_eq!(triangle.barycentric_at(points[0]), Some([1.0, 0.0, 0.0]));
+        assert_eq!(triangle.barycentric_at(points[1]), Some([0.0, 1.0, 0.0]));
+        assert_eq!(triangle.barycentric_at(points[2]), Some([0.0, 0.0, 1.0]));
+
+        let centroid = triangle.barycentric_at((10.0 / 3.0, 3.0)).unwrap();
+        for &w in &centroid {
+            assert!((w - 1.0 / 3.0).abs() < 1e-9);
+        }
+
+        for (p, weights) in triangle {
+            let at = triangle.barycentric_at(p).unwrap();
+            for (a, w) in at.iter().zip(&weights) {
+                assert!((a - w).abs() < 1e-9);
+            }
+        }
+
+        let line = Triangle::with_points([(0.0, 0.0), (1.0, 1.0), (2.0, 2.0)]);
+        assert_eq!(line.barycentric_at((1.0, 1.0)), None);
+    }
+
+    #[test]
+    fn clip_to_rect() {
+        use rect::Rectangle;
+        use super::Winding;
+
+        let rect = Rectangle::new(0.0, 4.0, 0.0, 4.0);
+        let inside = Triangle::with_points([(1.0, 1.0), (3.0, 1.0), (1.0, 4.0)]);
+        assert_eq!(inside.clip_to_rect(&rect), [inside]);
+
+        let outside = Triangle::with_points([(5.0, 5.0), (9.0, 5.0), (5.0, 9.0)]);
+        assert!(outside.clip_to_rect(&rect).is_empty());
+        // the bounding box overlaps, but the triangle doesn't
+        let corner = Triangle::with_points([(3.0, 6.0), (6.0, 3.0), (6.0, 6.0)]);
+        assert!(corner.clip_to_rect(&rect).is_empty());
+
+        // the corner at (6, 1) pokes out through the right edge
+        let poking = Triangle::with_points([(1.0, 1.0), (6.0, 1.0), (1.0, 3.0)]);
+        let clipped = poking.clip_to_rect(&rect);
+        assert!(!clipped.is_empty() && clipped.len() <= 3);
+        let area: f64 = clipped.iter().map(|tri| tri.area()).sum();
+        // the cut off corner is (4, 1), (6, 1), (4, 1.8)
+        assert!((area - (poking.area() - 0.8)).abs() < 1e-9);
+        for tri in &clipped {
+            assert!(tri.points().iter().all(|p| p.0 <= 4.0));
+            assert_eq!(tri.is_front_facing(Winding::Ccw), poking.is_front_facing(Winding::Ccw));
+        }
+    }
+
+    #[test]
+    fn into_iter_edges() {
+        let triangle = Triangle::with_points([(0.0, 0.0), (8.0, 0.0), (0.0, 8.0)]);
+        let fragments = triangle.into_iter_edges(0.15).collect::<Vec<_>>();
+        assert_eq!(fragments.len(), triangle.into_iter().count());
+        let mask = |p| fragments.iter().find(|f| f.0 == p).unwrap().2;
+
+        // the vertices lie on the edges meeting there
+        let corner = mask((0.0, 0.0));
+        assert_eq!(corner.count(), 2);
+        assert!(corner.contains(0) && corner.contains(2));
+        assert!(mask((7.0, 0.0)).contains(0) && mask((7.0, 0.0)).contains(1));
+        assert!(mask((0.0, 7.0)).contains(1) && mask((0.0, 7.0)).contains(2));
+
+        let bottom = mask((4.0, 0.0));
+        assert_eq!(bottom.count(), 1);
+        assert!(bottom.contains(0));
+        assert!(mask((4.0, 4.0)).contains(1));
+        assert!(mask((2.0, 2.0)).is_empty());
+    }
+
+    #[test]
+    fn winding() {
+        use super::Winding;
+
+        let ccw = Triangle::with_points([(0.0, 0.0), (4.0, 0.0), (0.0, 4.0)]);
+        let cw = Triangle::with_points([(0.0, 0.0), (0.0, 4.0), (4.0, 0.0)]);
+        assert!(ccw.is_front_facing(Winding::Ccw));
+        assert!(!ccw.is_front_facing(Winding::Cw));
+        assert!(cw.is_front_facing(Winding::Cw));
+        assert!(!cw.is_front_facing(Winding::Ccw));
+
+        let sliver = Triangle::with_points([(0.0, 0.0), (1e6, 1e-11), (2e6, 0.0)]);
+        assert!(!sliver.is_front_facing(Winding::Cw));
+        assert!(!sliver.is_front_facing(Winding::Ccw));
+    }
+
+    #[test]
+    fn edges() {
+        let points = [(1.0, 1.0), (9.0, 3.0), (4.0, 7.0)];
+        let edges = Triangle::with_points(points).edges();
+
+        let loops = edges.iter().zip(edges.iter().cycle().skip(1));
+        for (edge, next) in loops {
+            let end = edge.into_iter().last().unwrap().0;
+            let start = next.into_iter().next().unwrap().0;
+            assert_eq!(end, start);
+        }
+
+        let pixels = edges
+            .iter()
+            .flat_map(|edge| edge.into_iter())
+            .map(|(p, _)| p)
+            .collect::<Vec<_>>();
+        assert!(points.iter().all(|p| pixels.contains(p)));
+    }
+
+    /// Iterate over the triangle by testing every pixel of its bounding box
+    /// with the plain barycentric formula.
+    fn brute_force(triangle: Triangle<f64>) -> Vec<((f64, f64), [f64; 3])> {
+        let det = triangle.det();
+        if det == 0.0 {
+            return Vec::new();
+        }
+
+        triangle
+            .bounding_box()
+            .into_iter()
+            .map(|p| (p, super::weights(triangle.points(), det, p)))
+            .filter(|&(_, w)| super::inside(w))
+            .collect()
+    }
+
+    fn assert_matches(triangle: Triangle<f64>) {
+        let expected = brute_force(triangle);
+        let fragments = triangle.into_iter().collect::<Vec<_>>();
+
+        assert_eq!(fragments.len(), expected.len());
+        for ((p, w), (q, v)) in fragments.into_iter().zip(expected) {
+            assert_eq!(p, q);
+            for i in 0..3 {
+                assert!((w[i] - v[i]).abs() < 1e-9);
+            }
+        }
+    }
+
+    #[test]
+    fn precomputed() {
+        assert_matches(Triangle::with_points([(0.5, 0.25), (30.0, 4.5), (12.75, 21.0)]));
+        assert_matches(Triangle::with_points([(0.5, 0.25), (12.75, 21.0), (30.0, 4.5)]));
+        assert_matches(Triangle::with_points([(0.0, 0.0), (16.0, 0.0), (0.0, 16.0)]));
+    }
+
+    #[test]
+    fn incremental() {
+        use rand::{Rng, SeedableRng, XorShiftRng};
+
+        let mut rng = XorShiftRng::from_seed([0x193a_6754, 0xa8a7_d469, 0x9783_0e05, 0x113b_a7bb]);
+        for _ in 0..500 {
+            let mut point = || (rng.gen::<f64>() * 64.0 - 8.0, rng.gen::<f64>() * 64.0 - 8.0);
+            assert_matches(Triangle::with_points([point(), point(), point()]));
+        }
+    }
+
+    #[test]
+    fn size_hint() {
+        use rand::{Rng, SeedableRng, XorShiftRng};
+
+        let mut rng = XorShiftRng::from_seed([0x2545_f491, 0x4f6c_dd1d, 0x9e37_79b9, 0x7f4a_7c15]);
+        for _ in 0..200 {
+            let mut point = || (rng.gen::<f64>() * 64.0 - 8.0, rng.gen::<f64>() * 64.0 - 8.0);
+            let triangle = Triangle::with_points([point(), point(), point()]);
+            let count = triangle.into_iter().count();
+
+            let mut iter = triangle.into_iter();
+            assert!(iter.size_hint().1.unwrap() >= count);
+            iter.nth(count / 2);
+            assert!(iter.size_hint().1.unwrap() >= iter.count());
+        }
+    }
+
+    #[test]
+    fn derivatives() {
+        // attr = 10x + 3y + 1
+        let attr = [1.0, 41.0, 13.0];
+        let lerp = |p: [f64; 3]| p[0] * attr[0] + p[1] * attr[1] + p[2] * attr[2];
+        let iter = Triangle::with_points([(0.0, 0.0), (4.0, 0.0), (0.0, 4.0)]).into_iter();
+
+        assert!((iter.ddx(attr) - 10.0).abs() < 1e-9);
+        assert!((iter.ddy(attr) - 3.0).abs() < 1e-9);
+
+        let coords = iter.collect::<Vec<_>>();
+        let ((x0, y0), p0) = coords[0];
+        let ((x1, y1), p1) = coords[1];
+        assert_eq!((x1 - x0, y1 - y0), (1.0, 0.0));
+        assert!((lerp(p1) - lerp(p0) - 10.0).abs() < 1e-9);
+    }
+
+    #[test]
+    fn degenerate() {
+        let collinear = Triangle::with_points([(0.0, 0.0), (1.0, 1.0), (2.0, 2.0)]);
+        assert_eq!(collinear.into_iter().next(), None);
+
+        let point = Triangle::with_points([(1.0, 1.0), (1.0, 1.0), (1.0, 1.0)]);
+        assert_eq!(point.into_iter().next(), None);
+    }
+
+    #[test]
+    fn aa() {
+        let triangle = Triangle::with_points([(0.0, 0.0), (8.0, 0.0), (0.0, 8.0)]);
+        let coverage = |x, y| {
+            triangle
+                .into_iter_aa()
+                .find(|&(((px, py), _), _)| (px, py) == (x, y))
+                .map(|(_, coverage)| coverage)
+        };
+
+        assert_eq!(coverage(2.0, 2.0), Some(1.0));
+        let edge = coverage(4.0, 4.0).unwrap();
+        assert!(edge > 0.0 && edge < 1.0);
+        assert_eq!(coverage(6.0, 6.0), None);
+    }
+
+    #[test]
+    fn clipped() {
+        let triangle = Triangle::with_points([(1.0, 1.0), (1e6, 1.0), (1.0, 1e6)]);
+        let clip = Rectangle::new(0.0, 16.0, 0.0, 16.0);
+        let pixels = triangle.into_iter_clipped(&clip).collect::<Vec<_>>();
+
+        assert_eq!(pixels.len(), 15 * 15);
+        assert!(
+            pixels
+                .iter()
+                .all(|&((x, y), _)| (1.0..16.0).contains(&x) && (1.0..16.0).contains(&y))
+        );
+
+        let degenerate = Triangle::with_points([(0.0, 0.0), (1.0, 1.0), (2.0, 2.0)]);
+        assert_eq!(degenerate.into_iter_clipped(&clip).next(), None);
+
+        // the unclipped bounding box doesn't even fit into an `i64`
+        let far = Triangle::with_points([(0.0, 0.0), (1e10, 0.0), (0.0, 1e10)]);
+        let pixels = far.into_iter_clipped(&clip).collect::<Vec<_>>();
+        assert_eq!(pixels.len(), 16 * 16);
+        assert!(
+            pixels
+                .iter()
+                .all(|&((x, y), _)| (0.0..16.0).contains(&x) && (0.0..16.0).contains(&y))
+        );
+        let far = Triangle::with_points([(-1e20, -1e20), (1e20, -1e20), (0.0, 1e20)]);
+        assert_eq!(far.into_iter_clipped(&clip).count(), 16 * 16);
+    }
+
+    #[test]
+    fn nonempty() {
+        let triangle = Triangle::with_points([(5.1, 5.1), (5.5, 5.1), (5.1, 5.5)]);
+        assert_eq!(triangle.into_iter().count(), 0);
+
+        let coords = triangle.into_iter_nonempty().collect::<Vec<_>>();
+        assert_eq!(coords.len(), 1);
+        assert_eq!(coords[0].0, (5.0, 5.0));
+
+        let line = Triangle::with_points([(5.25, 5.25), (5.5, 5.5), (5.75, 5.75)]);
+        assert_eq!(line.into_iter_nonempty().count(), 0);
+
+        // the centroid at (-0.3, 2.2) lies in the pixel left of the y axis
+        let negative = Triangle::with_points([(-0.4, 2.1), (-0.2, 2.1), (-0.3, 2.4)]);
+        assert_eq!(negative.into_iter().count(), 0);
+        let coords = negative.into_iter_nonempty().collect::<Vec<_>>();
+        assert_eq!(coords.len(), 1);
+        assert_eq!(coords[0].0, (-1.0, 2.0));
+    }
+
+    #[test]
+    fn watertight() {
+        use std::collections::HashSet;
+
+        let pixels = |triangle: Triangle<f64>| {
+            triangle
+                .into_iter_watertight()
+                .map(|((x, y), _)| (x as i64, y as i64))
+                .collect::<HashSet<_>>()
+        };
+
+        let quads = [
+            [(0.0, 0.0), (8.0, 0.0), (8.0, 8.0), (0.0, 8.0)],
+            [(0.5, 1.25), (9.75, 0.5), (11.0, 7.5), (1.5, 9.0)],
+            [(2.0, 0.0), (6.0, 4.0), (2.0, 8.0), (-2.0, 4.0)],
+        ];
+        for q in &quads {
+            // both windings of the shared diagonal
+            let a = pixels(Triangle::with_points([q[0], q[1], q[2]]));
+            let b = pixels(Triangle::with_points([q[0], q[3], q[2]]));
+            assert!(a.is_disjoint(&b));
+        }
+
+        let a = pixels(Triangle::with_points([(0.0, 0.0), (8.0, 0.0), (8.0, 8.0)]));
+        let b = pixels(Triangle::with_points([(0.0, 0.0), (8.0, 8.0), (0.0, 8.0)]));
+        let square = Rectangle::new(0, 8, 0, 8).into_iter().collect::<HashSet<_>>();
+        assert_eq!(a.union(&b).cloned().collect::<HashSet<_>>(), square);
+    }
+
+    #[test]
+    fn interp() {
+        let triangle = Triangle::with_points([(1.0, 1.0), (30.0, 4.0), (9.0, 25.0)]);
+        let (r0, r1, r2) = (255.0, 0.0, 0.0);
+        let (g0, g1, g2) = (0.0, 255.0, 0.0);
+        let (b0, b1, b2) = (0.0, 0.0, 255.0);
+
+        let colors = [(r0, g0, b0), (r1, g1, b1), (r2, g2, b2)];
+        let interp = triangle.into_iter_interp(colors).collect::<Vec<_>>();
+        let manual = triangle
+            .into_iter()
+            .map(|(xy, p)| {
+                let r = p[0] * r0 + p[1] * r1 + p[2] * r2;
+                let g = p[0] * g0 + p[1] * g1 + p[2] * g2;
+                let b = p[0] * b0 + p[1] * b1 + p[2] * b2;
+                (xy, (r, g, b))
+            })
+            .collect::<Vec<_>>();
+        assert!(!interp.is_empty());
+        assert_eq!(interp, manual);
+
+        let uvs = [[0.0, 0.0], [1.0, 0.0], [0.0, 1.0]];
+        for ((_, uv), (_, w)) in triangle.into_iter_interp(uvs).zip(triangle) {
+            assert_eq!(uv, [w[1], w[2]]);
+        }
+    }
+
+    #[test]
+    #[cfg(feature = "image")]
     fn random_tri() {
         const BPP: usize = 24;
         const WIDTH: usize = 1024;
@@ -0,0 +1,134 @@
+use num_traits::{AsPrimitive, Float};
+
+use renderer::Drawable;
+use point::Point2;
+use tri::{self, Coordinate, Triangle};
+
+/// A projected vertex: a screenspace point and the `w` it was divided by.
+pub type Vertex<T> = (Point2<T>, T);
+
+/// A triangle with perspective-correct barycentric interpolation.
+///
+/// Every vertex retains the `w` coordinate it was divided by during
+/// projection. The barycentric weights of each `Coordinate` are corrected
+/// for the perspective, so attributes interpolated with them are linear in
+/// eye space rather than in screen space. All `w` must be nonzero.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Triangle3<T> {
+    vertices: [Vertex<T>; 3],
+}
+
+impl<T: Float + AsPrimitive<i64>> Triangle3<T> {
+    #[inline(always)]
+    pub fn with_points(vertices: [Vertex<T>; 3]) -> Triangle3<T> {
+        Triangle3 { vertices }
+    }
+
+    /// Get the screenspace `Triangle`, which interpolates linearly.
+    #[inline]
+    pub fn screen(&self) -> Triangle<T> {
+        let v = self.vertices;
+        Triangle::with_points([v[0].0, v[1].0, v[2].0])
+    }
+}
+
+impl<T: Float + AsPrimitive<i64> + Copy + 'static> Drawable<T, Coordinate<T>> for Triangle3<T>
+where
+    i64: AsPrimitive<T>,
+{
+    #[inline(always)]
+    fn vertices(&self) -> usize {
+        3
+    }
+
+    #[inline]
+    fn into_iter_viewport(self, width: usize, height: usize) -> IntoIter<T> {
+        let v = self.vertices;
+
+        IntoIter {
+            inner: self.screen().into_iter_viewport(width, height),
+            w_recip: [v[0].1.recip(), v[1].1.recip(), v[2].1.recip()],
+        }
+    }
+}
+
+impl<T: Float + AsPrimitive<i64> + Copy + 'static> IntoIterator for Triangle3<T>
+where
+    i64: AsPrimitive<T>,
+{
+    type Item = Coordinate<T>;
+    type IntoIter = IntoIter<T>;
+
+    #[inline]
+    fn into_iter(self) -> Self::IntoIter {
+        let v = self.vertices;
+
+        IntoIter {
+            inner: self.screen().into_iter(),
+            w_recip: [v[0].1.recip(), v[1].1.recip(), v[2].1.recip()],
+        }
+    }
+}
+
+#[derive(Debug)]
+pub struct IntoIter<T> {
+    inner: tri::IntoIter<T>,
+    w_recip: [T; 3],
+}
+
+impl<T: Float + Copy + 'static> Iterator for IntoIter<T>
+where
+    i64: AsPrimitive<T>,
+{
+    type Item = Coordinate<T>;
+
+    #[inline]
+    fn next(&mut self) -> Option<Self::Item> {
+        let w = self.w_recip;
+
+        self.inner.next().map(|(p, b)| {
+            let b = [b[0] * w[0], b[1] * w[1], b[2] * w[2]];
+            let sum_recip = (b[0] + b[1] + b[2]).recip();
+            (p, [b[0] * sum_recip, b[1] * sum_recip, b[2] * sum_recip])
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Triangle3;
+
+    #[test]
+    fn perspective() {
+        // a triangle in eye space, receding with y, i.e. the half of a floor
+        // quad which projects to a trapezoid
+        let eye = [(0.0, 0.0, 1.0), (12.0, 0.0, 1.0), (0.0, 36.0, 3.0)];
+        let project = |(x, y, w): (f64, f64, f64)| ((x / w, y / w), w);
+        let triangle = Triangle3::with_points([
+            project(eye[0]),
+            project(eye[1]),
+            project(eye[2]),
+        ]);
+
+        // the screenspace centroid is at (4, 4)
+        let (_, b) = triangle
+            .into_iter()
+            .find(|&(p, _)| p == (4.0, 4.0))
+            .unwrap();
+        let expected = [3.0 / 7.0, 3.0 / 7.0, 1.0 / 7.0];
+        for i in 0..3 {
+            assert!((b[i] - expected[i]).abs() < 1e-9);
+        }
+
+        // every fragment must be the projection of the eye space point
+        // interpolated with the corrected weights
+        for ((x, y), b) in triangle {
+            let lerp = |f: &dyn Fn((f64, f64, f64)) -> f64| {
+                b[0] * f(eye[0]) + b[1] * f(eye[1]) + b[2] * f(eye[2])
+            };
+            let w = lerp(&|v| v.2);
+            assert!((lerp(&|v| v.0) / w - x).abs() < 1e-9);
+            assert!((lerp(&|v| v.1) / w - y).abs() < 1e-9);
+        }
+    }
+}
@@ -0,0 +1,80 @@
+//! Presentation of the front buffer of a `SimpleRenderer` in a `minifb`
+//! window.
+
+use minifb::{Result, Window};
+
+use num_traits::{AsPrimitive, Float, Signed};
+
+use renderer::{Renderer, SimpleRenderer};
+
+/// A trait for pixel types, which can be packed into the `u32` ARGB format
+/// `minifb` expects, i.e. to `present` them.
+pub trait ToArgb {
+    /// Pack the pixel into a `u32` with the channels ordered ARGB from the
+    /// most significant byte.
+    fn to_argb(&self) -> u32;
+}
+
+/// Pack the channels into a `u32` ARGB pixel.
+#[inline(always)]
+fn argb(r: u8, g: u8, b: u8, a: u8) -> u32 {
+    u32::from(a) << 24 | u32::from(r) << 16 | u32::from(g) << 8 | u32::from(b)
+}
+
+/// Without an alpha channel the pixels are opaque.
+impl ToArgb for (u8, u8, u8) {
+    #[inline(always)]
+    fn to_argb(&self) -> u32 {
+        argb(self.0, self.1, self.2, 255)
+    }
+}
+
+/// `minifb` ignores the alpha channel unless the window is transparent.
+impl ToArgb for (u8, u8, u8, u8) {
+    #[inline(always)]
+    fn to_argb(&self) -> u32 {
+        argb(self.0, self.1, self.2, self.3)
+    }
+}
+
+impl<Px: ToArgb + Clone, T: Float + Signed + AsPrimitive<usize>> SimpleRenderer<Px, T> {
+    /// Pack the front buffer into ARGB pixels.
+    pub fn to_argb(&self) -> Vec<u32> {
+        self.buffer().iter().map(ToArgb::to_argb).collect()
+    }
+
+    /// Show the front buffer, i.e. the last frame `swap`-ped in, in
+    /// `window`. This also processes the input events of the window.
+    pub fn present(&self, window: &mut Window) -> Result<()> {
+        let width = Renderer::<T>::width(self);
+        let height = Renderer::<T>::height(self);
+        window.update_with_buffer(&self.to_argb(), width, height)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::iter;
+
+    use renderer::{Renderer, SimpleRenderer};
+    use rect::Rectangle;
+
+    #[test]
+    fn to_argb() {
+        let mut renderer = SimpleRenderer::new(8, 4);
+        renderer.set_attr(0, (0x10, 0x20, 0x30));
+        assert!(renderer.draw(iter::once(Rectangle::new(2.0, 6.0, 1.0, 3.0))).is_ok());
+        renderer.swap();
+
+        let argb = renderer.to_argb();
+        assert_eq!(argb.len(), 8 * 4);
+        assert_eq!(argb[2 * 8 + 3], 0xff10_2030);
+        assert_eq!(argb[2 * 8 + 6], 0xff00_0000);
+
+        let mut renderer = SimpleRenderer::new(8, 4);
+        renderer.set_attr(0, (0x10, 0x20, 0x30, 0x40));
+        assert!(renderer.draw(iter::once(Rectangle::new(2.0, 6.0, 1.0, 3.0))).is_ok());
+        renderer.swap();
+        assert_eq!(renderer.to_argb()[8 + 5], 0x4010_2030);
+    }
+}